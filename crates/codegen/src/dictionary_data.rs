@@ -0,0 +1,16 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+pub fn generate() -> TokenStream {
+    let data = include_str!("../data/dictionary.txt");
+
+    let words = data
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+    quote! {
+        [#(#words),*]
+    }
+    .into_token_stream()
+}
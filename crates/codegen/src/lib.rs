@@ -5,11 +5,13 @@ pub(crate) mod common;
 mod bodypart_data;
 mod broken_data;
 mod class_data;
+mod dictionary_data;
 mod mwscript_data;
 mod project_data;
 mod services_data;
 mod spells_data;
 mod supplies_data;
+mod terms_data;
 mod travel_data;
 mod uniques_data;
 
@@ -28,11 +30,26 @@ pub fn get_class_data(_: TokenStream) -> TokenStream {
     class_data::generate().into()
 }
 
+#[proc_macro]
+pub fn get_dictionary(_: TokenStream) -> TokenStream {
+    dictionary_data::generate().into()
+}
+
 #[proc_macro]
 pub fn get_joined_commands(_: TokenStream) -> TokenStream {
     mwscript_data::generate_joined_commands().into()
 }
 
+#[proc_macro]
+pub fn get_function_signatures(input: TokenStream) -> TokenStream {
+    let engine = if input.is_empty() {
+        String::new()
+    } else {
+        syn::parse_macro_input!(input as syn::LitStr).value()
+    };
+    mwscript_data::generate_function_signatures(&engine).into()
+}
+
 #[proc_macro]
 pub fn get_khajiit_script(_: TokenStream) -> TokenStream {
     mwscript_data::generate_khajiit_script().into()
@@ -58,6 +75,11 @@ pub fn get_supplies_data(_: TokenStream) -> TokenStream {
     supplies_data::generate().into()
 }
 
+#[proc_macro]
+pub fn get_term_rules(_: TokenStream) -> TokenStream {
+    terms_data::generate().into()
+}
+
 #[proc_macro]
 pub fn get_travel_classes(_: TokenStream) -> TokenStream {
     travel_data::generate().into()
@@ -1,5 +1,5 @@
 use proc_macro2::TokenStream;
-use quote::ToTokens;
+use quote::{quote, ToTokens};
 use regex::Regex;
 
 pub fn generate_joined_commands() -> TokenStream {
@@ -26,3 +26,39 @@ pub fn generate_khajiit_script() -> TokenStream {
 
     khajiit_input.into_token_stream()
 }
+
+pub fn generate_function_signatures(engine: &str) -> TokenStream {
+    let data = match engine {
+        "" | "vanilla" => include_str!("../data/mwscript.functions.txt"),
+        "openmw" => include_str!("../data/mwscript.functions.openmw.txt"),
+        "mwse" => include_str!("../data/mwscript.functions.mwse.txt"),
+        _ => panic!("Unknown engine {}", engine),
+    };
+
+    let signatures = data.lines().filter_map(|line| {
+        if line.starts_with('#') {
+            return None;
+        }
+        let mut parts = line.split_whitespace();
+        let name = parts.next()?.to_ascii_lowercase();
+        let min: usize = parts.next()?.parse().unwrap();
+        let max = match parts.next() {
+            Some("*") => quote! { None },
+            Some(max) => {
+                let max: usize = max.parse().unwrap();
+                quote! { Some(#max) }
+            }
+            None => quote! { Some(#min) },
+        };
+        Some(quote! { map.insert(#name, (#min, #max)); })
+    });
+
+    quote! {
+        {
+            let mut map = std::collections::HashMap::new();
+            #( #signatures )*
+            map
+        }
+    }
+    .into_token_stream()
+}
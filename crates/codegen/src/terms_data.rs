@@ -0,0 +1,57 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::common::_Vec;
+
+const SCOPES: [&str; 7] = [
+    "all",
+    "books",
+    "descriptions",
+    "dialogue",
+    "journal",
+    "names",
+    "scripts",
+];
+
+fn default_scope() -> String {
+    String::from("all")
+}
+
+#[derive(Deserialize)]
+pub struct TermRule {
+    pub pattern: String,
+    pub replacement: String,
+    #[serde(default = "default_scope")]
+    pub scope: String,
+}
+
+impl ToTokens for TermRule {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let pattern = &self.pattern;
+        let replacement = &self.replacement;
+        let scope = &self.scope;
+        quote! {
+            TermRule {
+                pattern: #pattern,
+                replacement: #replacement,
+                scope: #scope,
+            }
+        }
+        .to_tokens(tokens);
+    }
+}
+
+pub fn generate() -> TokenStream {
+    let data: _Vec<TermRule> = serde_json::from_str(include_str!("../data/terms.json")).unwrap();
+    for rule in &data.0 {
+        if let Err(e) = Regex::new(&rule.pattern) {
+            panic!("Invalid term pattern {}: {}", rule.pattern, e);
+        }
+        if !SCOPES.contains(&rule.scope.as_str()) {
+            panic!("Invalid scope {} for term {}", rule.scope, rule.pattern);
+        }
+    }
+    data.into_token_stream()
+}
@@ -1,4 +1,4 @@
-use crate::util::ci_starts_with;
+use crate::{encoding::Encoding, util::ci_starts_with};
 use codegen::get_project_data;
 
 #[derive(Clone, PartialEq)]
@@ -25,6 +25,41 @@ where
     }
 }
 
+#[derive(Clone, Copy, PartialEq)]
+pub enum Engine {
+    Vanilla,
+    Mcp,
+    OpenMw,
+    Mwse,
+}
+
+impl<T> From<T> for Engine
+where
+    T: AsRef<str>,
+{
+    fn from(value: T) -> Self {
+        match value.as_ref() {
+            "mcp" => Engine::Mcp,
+            "openmw" => Engine::OpenMw,
+            "mwse" => Engine::Mwse,
+            _ => Engine::Vanilla,
+        }
+    }
+}
+
+impl Engine {
+    pub const ALL: [Engine; 4] = [Engine::Vanilla, Engine::Mcp, Engine::OpenMw, Engine::Mwse];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Engine::Vanilla => "Morrowind.exe",
+            Engine::Mcp => "the Morrowind Code Patch",
+            Engine::OpenMw => "OpenMW",
+            Engine::Mwse => "MWSE",
+        }
+    }
+}
+
 pub struct Project {
     pub name: &'static str,
     pub prefix: &'static str,
@@ -43,13 +78,20 @@ impl Project {
 
 pub struct Context {
     pub mode: Mode,
+    pub engine: Engine,
+    pub encoding: Encoding,
+    /// Whether the text follows French typography
+    pub french: bool,
     pub projects: Vec<Project>,
 }
 
 impl Context {
-    pub fn new(mode: Mode) -> Self {
+    pub fn new(mode: Mode, engine: Engine, encoding: Encoding, french: bool) -> Self {
         Context {
             mode,
+            engine,
+            encoding,
+            french,
             projects: get_project_data!(),
         }
     }
@@ -1,16 +1,34 @@
 use clap::ArgMatches;
 use tes3::esp::{Dialogue, DialogueInfo, TES3Object};
 
+use crate::context::Mode;
+
 use self::{
     cells::CellValidator,
+    chains::ChainValidator,
+    choices::ChoiceValidator,
+    filters::FilterValidator,
+    identifiers::IdentifierValidator,
     items::OwnershipValidator,
+    journals::JournalValidator,
     names::{NameValidator, QuestNameValidator},
+    shadowing::ShadowValidator,
+    speakers::SpeakerValidator,
+    topics::TopicValidator,
     weapons::WeaponValidator,
 };
 
 mod cells;
+mod chains;
+mod choices;
+mod filters;
+mod identifiers;
 mod items;
+mod journals;
 mod names;
+mod shadowing;
+mod speakers;
+mod topics;
 mod weapons;
 
 pub struct ExtendedValidator {
@@ -32,8 +50,22 @@ impl ExtendedValidator {
         let extended = args.get_flag("extended");
         let names = args.get_flag("names");
         if extended {
+            let mode = args
+                .get_one::<String>("mode")
+                .map_or(Mode::None, Mode::from);
             handlers.push(Box::new(CellValidator::new(args)));
+            handlers.push(Box::new(ChainValidator::new()));
+            handlers.push(Box::new(ChoiceValidator::new()));
+            handlers.push(Box::new(FilterValidator::new()));
+            handlers.push(Box::new(IdentifierValidator::new()));
+            handlers.push(Box::new(JournalValidator::new()));
             handlers.push(Box::new(OwnershipValidator::new()));
+            handlers.push(Box::new(ShadowValidator::new()));
+            handlers.push(Box::new(SpeakerValidator::new()));
+            // Topics in TD are made available by the plugins that use them
+            if mode != Mode::TD {
+                handlers.push(Box::new(TopicValidator::new()));
+            }
             handlers.push(Box::new(WeaponValidator::new()));
         }
         if names {
@@ -0,0 +1,64 @@
+use std::collections::{HashMap, HashSet};
+
+use tes3::esp::{Dialogue, DialogueInfo};
+
+use super::ExtendedHandler;
+
+struct Link {
+    topic: String,
+    id: String,
+    prev_id: String,
+    next_id: String,
+}
+
+pub struct ChainValidator {
+    topics: HashMap<String, HashSet<String>>,
+    links: Vec<Link>,
+}
+
+impl ExtendedHandler for ChainValidator {
+    fn on_info(&mut self, record: &DialogueInfo, topic: &Dialogue, _: &str, last: bool) {
+        let key = topic.id.to_ascii_lowercase();
+        self.topics
+            .entry(key)
+            .or_default()
+            .insert(record.id.clone());
+        if last {
+            self.links.push(Link {
+                topic: topic.id.clone(),
+                id: record.id.clone(),
+                prev_id: record.prev_id.clone(),
+                next_id: record.next_id.clone(),
+            });
+        }
+    }
+
+    fn on_end(&mut self) {
+        for link in &self.links {
+            let Some(infos) = self.topics.get(&link.topic.to_ascii_lowercase()) else {
+                continue;
+            };
+            if !link.prev_id.is_empty() && !infos.contains(&link.prev_id) {
+                println!(
+                    "Info {} in topic {} follows info {} which does not exist",
+                    link.id, link.topic, link.prev_id
+                );
+            }
+            if !link.next_id.is_empty() && !infos.contains(&link.next_id) {
+                println!(
+                    "Info {} in topic {} precedes info {} which does not exist",
+                    link.id, link.topic, link.next_id
+                );
+            }
+        }
+    }
+}
+
+impl ChainValidator {
+    pub fn new() -> Self {
+        Self {
+            topics: HashMap::new(),
+            links: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+use tes3::esp::{Dialogue, DialogueInfo, DialogueType2, ObjectFlags};
+
+use crate::{
+    mwscript::parse,
+    validators::choices::{get_answered, get_offered},
+};
+
+use super::ExtendedHandler;
+
+struct Info {
+    offered: Vec<i32>,
+    answered: Vec<i32>,
+    last: bool,
+}
+
+#[derive(Default)]
+struct Topic {
+    id: String,
+    infos: HashMap<String, Info>,
+}
+
+pub struct ChoiceValidator {
+    topics: HashMap<String, Topic>,
+}
+
+impl ExtendedHandler for ChoiceValidator {
+    fn on_info(&mut self, record: &DialogueInfo, topic: &Dialogue, _: &str, last: bool) {
+        if topic.dialogue_type == DialogueType2::Journal {
+            return;
+        }
+        let entry = self
+            .topics
+            .entry(topic.id.to_ascii_lowercase())
+            .or_default();
+        topic.id.clone_into(&mut entry.id);
+        if record.flags.contains(ObjectFlags::DELETED) {
+            entry.infos.remove(&record.id);
+            return;
+        }
+        let offered = if record.script_text.to_ascii_lowercase().contains("choice") {
+            get_offered(&parse(&record.script_text))
+        } else {
+            Vec::new()
+        };
+        // Later files replace the info
+        entry.infos.insert(
+            record.id.clone(),
+            Info {
+                offered,
+                answered: get_answered(record).collect(),
+                last,
+            },
+        );
+    }
+
+    fn on_end(&mut self) {
+        for topic in self.topics.values() {
+            if !topic.infos.values().any(|info| info.last) {
+                continue;
+            }
+            let mut infos: Vec<_> = topic.infos.iter().collect();
+            infos.sort_by_key(|(id, _)| *id);
+            let offered: Vec<_> = infos.iter().flat_map(|(_, info)| &info.offered).collect();
+            let answered: Vec<_> = infos.iter().flat_map(|(_, info)| &info.answered).collect();
+            // Only the infos in the checked file are reported
+            for (id, info) in infos.iter().filter(|(_, info)| info.last) {
+                for value in &info.answered {
+                    if !offered.contains(&value) {
+                        println!(
+                            "Info {} in topic {} checks for choice {} which is never offered",
+                            id, topic.id, value
+                        );
+                    }
+                }
+                for value in &info.offered {
+                    if !answered.contains(&value) {
+                        println!(
+                            "Info {} in topic {} offers choice {} which no info answers",
+                            id, topic.id, value
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl ChoiceValidator {
+    pub fn new() -> Self {
+        Self {
+            topics: HashMap::new(),
+        }
+    }
+}
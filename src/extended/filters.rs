@@ -0,0 +1,31 @@
+use tes3::esp::{Dialogue, DialogueInfo, TES3Object};
+
+use crate::validators::filters::FilterTargets;
+
+use super::ExtendedHandler;
+
+pub struct FilterValidator {
+    targets: FilterTargets,
+}
+
+impl ExtendedHandler for FilterValidator {
+    fn on_record(&mut self, record: &TES3Object, _: &str, _: bool) {
+        self.targets.add_record(record);
+    }
+
+    fn on_info(&mut self, record: &DialogueInfo, topic: &Dialogue, _: &str, last: bool) {
+        self.targets.add_info(record, topic, last);
+    }
+
+    fn on_end(&mut self) {
+        self.targets.report(false);
+    }
+}
+
+impl FilterValidator {
+    pub fn new() -> Self {
+        Self {
+            targets: FilterTargets::new(),
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use tes3::esp::{Dialogue, DialogueInfo, TES3Object};
+
+use crate::{mwscript::parse, validators::identifiers::ScriptIdentifiers};
+
+use super::ExtendedHandler;
+
+pub struct IdentifierValidator {
+    identifiers: ScriptIdentifiers,
+}
+
+impl ExtendedHandler for IdentifierValidator {
+    fn on_record(&mut self, record: &TES3Object, _: &str, last: bool) {
+        self.identifiers.add_record(record);
+        if let TES3Object::Script(script) = record {
+            if last {
+                let source = format!("Script {}", script.id);
+                self.identifiers
+                    .add_script(&source, true, &parse(&script.text));
+            }
+        }
+    }
+
+    fn on_info(&mut self, record: &DialogueInfo, topic: &Dialogue, _: &str, last: bool) {
+        if last && !record.script_text.is_empty() {
+            let source = format!("Info {} in topic {}", record.id, topic.id);
+            self.identifiers
+                .add_script(&source, false, &parse(&record.script_text));
+        }
+    }
+
+    fn on_end(&mut self) {
+        self.identifiers.report(false);
+    }
+}
+
+impl IdentifierValidator {
+    pub fn new() -> Self {
+        Self {
+            identifiers: ScriptIdentifiers::new(),
+        }
+    }
+}
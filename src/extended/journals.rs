@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use tes3::esp::{Dialogue, DialogueInfo, DialogueType2, ObjectFlags, QuestState};
+
+use super::ExtendedHandler;
+
+#[derive(Default)]
+struct Journal {
+    id: String,
+    modified: bool,
+    named: bool,
+    finished: bool,
+}
+
+pub struct JournalValidator {
+    journals: HashMap<String, Journal>,
+}
+
+impl ExtendedHandler for JournalValidator {
+    fn on_info(&mut self, record: &DialogueInfo, topic: &Dialogue, _: &str, last: bool) {
+        if topic.dialogue_type != DialogueType2::Journal
+            || record.flags.contains(ObjectFlags::DELETED)
+        {
+            return;
+        }
+        let journal = self
+            .journals
+            .entry(topic.id.to_ascii_lowercase())
+            .or_default();
+        topic.id.clone_into(&mut journal.id);
+        journal.modified |= last;
+        match record.quest_state {
+            Some(QuestState::Name) => journal.named = true,
+            Some(QuestState::Finished) => journal.finished = true,
+            _ => {}
+        }
+    }
+
+    fn on_end(&mut self) {
+        for journal in self.journals.values() {
+            if !journal.modified {
+                continue;
+            }
+            if !journal.named {
+                println!("Journal {} does not have a quest name", journal.id);
+            }
+            if !journal.finished {
+                println!("Journal {} is never finished", journal.id);
+            }
+        }
+    }
+}
+
+impl JournalValidator {
+    pub fn new() -> Self {
+        Self {
+            journals: HashMap::new(),
+        }
+    }
+}
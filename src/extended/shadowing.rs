@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+
+use tes3::esp::{Dialogue, DialogueInfo};
+
+use crate::validators::shadowing::{is_selectable, shadows};
+
+use super::ExtendedHandler;
+
+struct Entry {
+    info: DialogueInfo,
+    file: String,
+    last: bool,
+}
+
+pub struct ShadowValidator {
+    topics: HashMap<String, (String, Vec<Entry>)>,
+}
+
+impl ExtendedHandler for ShadowValidator {
+    fn on_info(&mut self, record: &DialogueInfo, topic: &Dialogue, file: &str, last: bool) {
+        let (_, entries) = self
+            .topics
+            .entry(topic.id.to_ascii_lowercase())
+            .or_insert_with(|| (topic.id.clone(), Vec::new()));
+        let entry = Entry {
+            info: record.clone(),
+            file: file.to_string(),
+            last,
+        };
+        // Insert the info the same way the game does when merging topics
+        if let Some(existing) = entries.iter_mut().find(|e| e.info.id == record.id) {
+            *existing = entry;
+        } else if record.prev_id.is_empty() {
+            entries.insert(0, entry);
+        } else if let Some(index) = entries.iter().position(|e| e.info.id == record.prev_id) {
+            entries.insert(index + 1, entry);
+        } else {
+            entries.push(entry);
+        }
+    }
+
+    fn on_end(&mut self) {
+        for (id, entries) in self.topics.values() {
+            for (i, entry) in entries.iter().enumerate() {
+                if !entry.last || !is_selectable(&entry.info) {
+                    continue;
+                }
+                let shadowed_by = entries[..i].iter().find(|other| {
+                    !other.last && is_selectable(&other.info) && shadows(&other.info, &entry.info)
+                });
+                if let Some(other) = shadowed_by {
+                    println!(
+                        "Info {} in topic {} is shadowed by info {} from {}",
+                        entry.info.id, id, other.info.id, other.file
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl ShadowValidator {
+    pub fn new() -> Self {
+        Self {
+            topics: HashMap::new(),
+        }
+    }
+}
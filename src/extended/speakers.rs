@@ -0,0 +1,127 @@
+use std::collections::{HashMap, HashSet};
+
+use tes3::esp::{Dialogue, DialogueInfo, EditorId, ObjectFlags, TES3Object};
+
+use crate::{
+    mwscript::{parse, Statement},
+    util::{ci_starts_with, is_dead},
+};
+
+use super::ExtendedHandler;
+
+struct Speaker {
+    source: String,
+    id: String,
+    cell: String,
+}
+
+pub struct SpeakerValidator {
+    actors: HashMap<String, bool>,
+    placed: HashSet<String>,
+    cells: HashSet<String>,
+    speakers: Vec<Speaker>,
+}
+
+impl ExtendedHandler for SpeakerValidator {
+    fn on_record(&mut self, record: &TES3Object, _: &str, _: bool) {
+        match record {
+            TES3Object::Npc(_) | TES3Object::Creature(_) => {
+                self.actors.insert(
+                    record.editor_id_ascii_lowercase().into_owned(),
+                    is_dead(record),
+                );
+            }
+            TES3Object::Cell(cell) => {
+                if !cell.name.is_empty() {
+                    self.cells.insert(cell.name.to_ascii_lowercase());
+                }
+                for reference in cell.references.values() {
+                    self.placed.insert(reference.id.to_ascii_lowercase());
+                }
+            }
+            TES3Object::Region(region) => {
+                self.cells.insert(region.name.to_ascii_lowercase());
+            }
+            TES3Object::LeveledCreature(leveled) => {
+                for (id, _) in &leveled.creatures {
+                    self.placed.insert(id.to_ascii_lowercase());
+                }
+            }
+            TES3Object::Script(script) => self.add_script(&parse(&script.text)),
+            _ => {}
+        }
+    }
+
+    fn on_info(&mut self, record: &DialogueInfo, topic: &Dialogue, _: &str, last: bool) {
+        if !record.script_text.is_empty() {
+            self.add_script(&parse(&record.script_text));
+        }
+        if !last || record.flags.contains(ObjectFlags::DELETED) {
+            return;
+        }
+        if !record.speaker_id.is_empty() || !record.speaker_cell.is_empty() {
+            self.speakers.push(Speaker {
+                source: format!("Info {} in topic {}", record.id, topic.id),
+                id: record.speaker_id.clone(),
+                cell: record.speaker_cell.clone(),
+            });
+        }
+    }
+
+    fn on_end(&mut self) {
+        for speaker in &self.speakers {
+            let id = speaker.id.to_ascii_lowercase();
+            let placeholder = id.is_empty() || id == "dialog placeholder";
+            match self.actors.get(&id) {
+                _ if placeholder => {}
+                Some(true) => {
+                    println!("{} has speaker {} who is dead", speaker.source, speaker.id);
+                }
+                Some(false) if !self.placed.contains(&id) => {
+                    println!(
+                        "{} has speaker {} who is not placed in any cell or leveled list",
+                        speaker.source, speaker.id
+                    );
+                }
+                Some(false) => {}
+                None => println!("{} has undefined speaker {}", speaker.source, speaker.id),
+            }
+            if !speaker.cell.is_empty()
+                && !self
+                    .cells
+                    .iter()
+                    .any(|cell| ci_starts_with(cell, &speaker.cell))
+            {
+                println!(
+                    "{} has unknown speaker cell {}",
+                    speaker.source, speaker.cell
+                );
+            }
+        }
+    }
+}
+
+impl SpeakerValidator {
+    pub fn new() -> Self {
+        Self {
+            actors: HashMap::new(),
+            placed: HashSet::new(),
+            cells: HashSet::new(),
+            speakers: Vec::new(),
+        }
+    }
+
+    fn add_script(&mut self, statements: &[Statement]) {
+        for statement in statements {
+            statement.for_each_call(&mut |call| {
+                let placing = call.is("placeatme")
+                    || call.is("placeatpc")
+                    || call.is("placeitem")
+                    || call.is("placeitemcell");
+                if let (true, Some(id)) = (placing, call.get_id(0)) {
+                    self.placed.insert(id.to_ascii_lowercase());
+                }
+            });
+        }
+    }
+}
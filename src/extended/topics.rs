@@ -0,0 +1,103 @@
+use std::collections::{HashMap, HashSet};
+
+use regex::{escape, RegexSetBuilder};
+use tes3::esp::{Dialogue, DialogueInfo, DialogueType2, TES3Object};
+
+use crate::mwscript::{parse, StatementKind};
+
+use super::ExtendedHandler;
+
+struct Topic {
+    name: String,
+    new: bool,
+}
+
+pub struct TopicValidator {
+    topics: HashMap<String, Topic>,
+    added_topics: HashSet<String>,
+    texts: Vec<String>,
+}
+
+impl ExtendedHandler for TopicValidator {
+    fn on_record(&mut self, record: &TES3Object, _: &str, last: bool) {
+        match record {
+            TES3Object::Dialogue(dialogue) if dialogue.dialogue_type == DialogueType2::Topic => {
+                // Plugins that add infos to a topic from a master repeat its record
+                self.topics
+                    .entry(dialogue.id.to_ascii_lowercase())
+                    .or_insert_with(|| Topic {
+                        name: dialogue.id.clone(),
+                        new: last,
+                    });
+            }
+            TES3Object::Script(script) => self.add_script(&script.text),
+            _ => {}
+        }
+    }
+
+    fn on_info(&mut self, record: &DialogueInfo, topic: &Dialogue, _: &str, _: bool) {
+        if topic.dialogue_type != DialogueType2::Journal && !record.text.is_empty() {
+            self.texts.push(record.text.to_ascii_lowercase());
+        }
+        self.add_script(&record.script_text);
+    }
+
+    fn on_end(&mut self) {
+        let new: Vec<_> = self.topics.iter().filter(|(_, topic)| topic.new).collect();
+        if new.is_empty() {
+            return;
+        }
+        // Find every mention in a single pass over the texts
+        let Ok(set) = RegexSetBuilder::new(new.iter().map(|(id, _)| escape(id)))
+            .size_limit(100 << 20)
+            .build()
+        else {
+            return;
+        };
+        let mut mentioned = HashSet::new();
+        for text in &self.texts {
+            mentioned.extend(set.matches(text).iter());
+        }
+        for (i, (id, topic)) in new.iter().enumerate() {
+            if !mentioned.contains(&i) && !self.added_topics.contains(*id) {
+                println!("Topic {} is never made available", topic.name);
+            }
+            for (other_id, other) in &self.topics {
+                if other_id == *id {
+                    continue;
+                }
+                if other_id.contains(id.as_str()) {
+                    println!("Topic {} is contained in topic {}", topic.name, other.name);
+                } else if !other.new && id.contains(other_id.as_str()) {
+                    println!("Topic {} is contained in topic {}", other.name, topic.name);
+                }
+            }
+        }
+    }
+}
+
+impl TopicValidator {
+    pub fn new() -> Self {
+        Self {
+            topics: HashMap::new(),
+            added_topics: HashSet::new(),
+            texts: Vec::new(),
+        }
+    }
+
+    fn add_script(&mut self, text: &str) {
+        // Avoid parsing every script in the masters
+        if !text.to_ascii_lowercase().contains("addtopic") {
+            return;
+        }
+        for statement in parse(text) {
+            if let StatementKind::Call(call) = &statement.kind {
+                if call.is("addtopic") {
+                    if let Some(id) = call.get_id(0) {
+                        self.added_topics.insert(id.to_ascii_lowercase());
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,11 @@
+mod books;
+mod corpses;
+mod duplicates;
+mod keys;
+mod pathgrids;
+
+pub use books::fix_books;
+pub use corpses::fix_corpses;
+pub use duplicates::fix_duplicates;
+pub use keys::{fix_keys, get_master_miscs};
+pub use pathgrids::fix_pathgrids;
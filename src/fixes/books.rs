@@ -0,0 +1,95 @@
+use regex::{Captures, Regex, RegexBuilder};
+use tes3::esp::{Book, Plugin};
+
+use crate::{util::is_marker, validators::books::TAGS};
+
+fn get_tag_name(tag: &str) -> (bool, &str) {
+    let inner = tag.trim_start_matches('<').trim_end_matches('>');
+    let (closing, inner) = match inner.strip_prefix('/') {
+        Some(inner) => (true, inner),
+        None => (false, inner),
+    };
+    let end = inner
+        .find(|c: char| c.is_whitespace() || c == '/')
+        .unwrap_or(inner.len());
+    (closing, &inner[..end])
+}
+
+fn is_container(tag: &str) -> bool {
+    tag.eq_ignore_ascii_case("div") || tag.eq_ignore_ascii_case("font")
+}
+
+fn fix_html(html: &str, src: &Regex) -> String {
+    let mut output = String::with_capacity(html.len());
+    let mut open: Vec<&str> = Vec::new();
+    let mut ambiguous = false;
+    let mut invisible = false;
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        let Some(length) = rest[start..].find('>') else {
+            break;
+        };
+        let text = &rest[..start];
+        let tag = &rest[start..=start + length];
+        rest = &rest[start + length + 1..];
+        output.push_str(text);
+        if !text.trim().is_empty() {
+            invisible = true;
+        }
+        let (closing, name) = get_tag_name(tag);
+        if !TAGS.contains(&name.to_ascii_lowercase().as_str()) {
+            // Drop the tag but keep the text it contains
+            continue;
+        }
+        if closing {
+            if is_container(name) {
+                if open.last().is_some_and(|o| o.eq_ignore_ascii_case(name)) {
+                    open.pop();
+                } else {
+                    ambiguous = true;
+                }
+            }
+        } else {
+            invisible = false;
+            if is_container(name) {
+                open.push(name);
+            } else if name.eq_ignore_ascii_case("img") {
+                let fixed = src.replace(tag, |captures: &Captures| {
+                    format!("{}{}", &captures[1], captures[2].replace('/', "\\"))
+                });
+                output.push_str(&fixed);
+                continue;
+            }
+        }
+        output.push_str(tag);
+    }
+    output.push_str(rest);
+    if invisible || !rest.trim().is_empty() {
+        output.push_str("<BR>");
+    }
+    if !ambiguous {
+        for name in open.iter().rev() {
+            output.push_str("</");
+            output.push_str(name);
+            output.push('>');
+        }
+    }
+    output
+}
+
+pub fn fix_books(plugin: &mut Plugin) {
+    let src = RegexBuilder::new(r#"(\ssrc\s*=\s*"?)([^"\s>]*)"#)
+        .case_insensitive(true)
+        .build()
+        .unwrap();
+    for book in plugin.objects_of_type_mut::<Book>() {
+        if is_marker(book) || book.text.is_empty() {
+            continue;
+        }
+        let text = fix_html(&book.text, &src);
+        if text != book.text {
+            println!("Fixing HTML in Book {}", book.id);
+            book.text = text;
+        }
+    }
+}
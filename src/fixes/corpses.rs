@@ -0,0 +1,21 @@
+use tes3::esp::{EditorId, ObjectFlags, Plugin, TES3Object, TypeInfo};
+
+use crate::util::{is_dead, is_persistent};
+
+pub fn fix_corpses(plugin: &mut Plugin) {
+    for record in &mut plugin.objects {
+        if !is_dead(record) || is_persistent(record) {
+            continue;
+        }
+        match record {
+            TES3Object::Creature(creature) => creature.flags.insert(ObjectFlags::PERSISTENT),
+            TES3Object::Npc(npc) => npc.flags.insert(ObjectFlags::PERSISTENT),
+            _ => continue,
+        }
+        println!(
+            "Setting corpse persists on {} {}",
+            record.type_name(),
+            record.editor_id()
+        );
+    }
+}
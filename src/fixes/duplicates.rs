@@ -0,0 +1,115 @@
+use std::collections::HashSet;
+
+use regex::Regex;
+use tes3::esp::{Cell, EditorId, Plugin, Reference, TES3Object};
+
+use crate::{util::is_persistent, validators::duplicates::is_duplicate};
+
+fn get_scripted_ids(objects: &[TES3Object]) -> HashSet<String> {
+    let tokens = Regex::new(r#""([^"]+)"|[^\s,"]+"#).unwrap();
+    let mut ids = HashSet::new();
+    for record in objects {
+        let text = match record {
+            TES3Object::Script(script) => &script.text,
+            TES3Object::DialogueInfo(info) => &info.script_text,
+            _ => continue,
+        };
+        let lower = text.to_ascii_lowercase().replace("->", " ");
+        for captures in tokens.captures_iter(&lower) {
+            let token = captures.get(1).or_else(|| captures.get(0)).unwrap();
+            ids.insert(token.as_str().to_string());
+        }
+    }
+    ids
+}
+
+fn has_data(reference: &Reference) -> bool {
+    reference.owner.is_some()
+        || reference.owner_global.is_some()
+        || reference.owner_faction.is_some()
+        || reference.lock_level.is_some()
+        || reference.key.is_some()
+        || reference.trap.is_some()
+        || reference.soul.is_some()
+        || reference.destination.is_some()
+}
+
+// References with data are kept over those without, then master references over local ones,
+// then the lowest refnum wins.
+fn get_priority(reference: &Reference) -> (bool, bool, u32) {
+    (
+        !has_data(reference),
+        reference.mast_index == 0,
+        reference.refr_index,
+    )
+}
+
+pub fn fix_duplicates(plugin: &mut Plugin, threshold: f32) {
+    let mut protected = get_scripted_ids(&plugin.objects);
+    protected.extend(
+        plugin
+            .objects
+            .iter()
+            .filter(|record| is_persistent(record))
+            .map(|record| record.editor_id_ascii_lowercase().into_owned()),
+    );
+
+    for cell in plugin.objects_of_type_mut::<Cell>() {
+        let mut refs: Vec<_> = cell
+            .references
+            .iter()
+            .filter(|(_, reference)| reference.deleted != Some(true))
+            .map(|(key, reference)| (*key, reference))
+            .collect();
+        refs.sort_by_key(|(_, reference)| (reference.mast_index, reference.refr_index));
+
+        let mut removed = HashSet::new();
+        for (i, (key, reference)) in refs.iter().enumerate() {
+            if removed.contains(&i) || protected.contains(&reference.id.to_ascii_lowercase()) {
+                continue;
+            }
+            for (j, (other_key, other)) in refs.iter().enumerate().skip(i + 1) {
+                if removed.contains(&j) || !is_duplicate(reference, other, threshold) {
+                    continue;
+                }
+                if has_data(reference) && has_data(other) {
+                    println!(
+                        "Not removing {} {:?} or {:?} from {} as both have ownership or lock data",
+                        reference.id,
+                        key,
+                        other_key,
+                        cell.editor_id()
+                    );
+                    continue;
+                }
+                let (kept, duplicate) = if get_priority(reference) <= get_priority(other) {
+                    (key, j)
+                } else {
+                    (other_key, i)
+                };
+                println!(
+                    "Removing {} {:?} from {} as it duplicates {:?}",
+                    reference.id,
+                    refs[duplicate].0,
+                    cell.editor_id(),
+                    kept
+                );
+                removed.insert(duplicate);
+                if duplicate == i {
+                    break;
+                }
+            }
+        }
+
+        let keys: Vec<_> = removed.into_iter().map(|i| refs[i].0).collect();
+        for key in keys {
+            match cell.references.get_mut(&key) {
+                // Master references cannot be removed, only marked as deleted
+                Some(reference) if reference.mast_index != 0 => reference.deleted = Some(true),
+                _ => {
+                    cell.references.remove(&key);
+                }
+            }
+        }
+    }
+}
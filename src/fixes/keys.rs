@@ -0,0 +1,56 @@
+use std::collections::{HashMap, HashSet};
+
+use tes3::esp::{Cell, MiscItem, MiscItemFlags, Plugin, TES3Object};
+
+use crate::util::is_key;
+
+pub fn get_master_miscs(master: &Plugin, miscs: &mut HashMap<String, MiscItem>) {
+    for misc in master.objects_of_type::<MiscItem>() {
+        miscs.insert(misc.id.to_ascii_lowercase(), misc.clone());
+    }
+}
+
+pub fn fix_keys(plugin: &mut Plugin, check_ids: bool, master_miscs: &HashMap<String, MiscItem>) {
+    let used: HashSet<_> = plugin
+        .objects_of_type::<Cell>()
+        .flat_map(|cell| cell.references.values())
+        .filter(|reference| reference.deleted != Some(true))
+        .filter_map(|reference| reference.key.as_ref())
+        .map(|key| key.to_ascii_lowercase())
+        .collect();
+
+    let mut defined = HashSet::new();
+    for misc in plugin.objects_of_type_mut::<MiscItem>() {
+        let lower = misc.id.to_ascii_lowercase();
+        if !is_key(misc) && (used.contains(&lower) || check_ids && lower.contains("key")) {
+            misc.data.flags.insert(MiscItemFlags::KEY);
+            println!("Setting key flag on MiscItem {}", misc.id);
+        }
+        defined.insert(lower);
+    }
+
+    let mut overrides: Vec<_> = used
+        .iter()
+        .filter(|id| !defined.contains(*id))
+        .filter_map(|id| master_miscs.get(id))
+        .filter(|misc| !is_key(misc))
+        .cloned()
+        .collect();
+    if overrides.is_empty() {
+        return;
+    }
+    overrides.sort_by(|a, b| a.id.cmp(&b.id));
+    for misc in &mut overrides {
+        misc.data.flags.insert(MiscItemFlags::KEY);
+        println!("Overriding MiscItem {} to set its key flag", misc.id);
+    }
+    let index = plugin
+        .objects
+        .iter()
+        .rposition(|object| matches!(object, TES3Object::MiscItem(_)))
+        .map_or(plugin.objects.len(), |i| i + 1);
+    plugin.objects.splice(
+        index..index,
+        overrides.into_iter().map(TES3Object::MiscItem),
+    );
+}
@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+
+use tes3::esp::{EditorId, PathGrid, Plugin};
+
+fn fix_pathgrid(pathgrid: &mut PathGrid) -> (usize, usize) {
+    let points = &pathgrid.points;
+
+    // Map every node onto the first node at the same location
+    let mut first_at: HashMap<[i32; 3], usize> = HashMap::new();
+    let canonical: Vec<usize> = points
+        .iter()
+        .enumerate()
+        .map(|(i, point)| *first_at.entry(point.location).or_insert(i))
+        .collect();
+
+    let mut edges: Vec<Vec<usize>> = vec![Vec::new(); points.len()];
+    let mut offset = 0;
+    for (i, point) in points.iter().enumerate() {
+        let count = point.connection_count as usize;
+        let from = canonical[i];
+        for &to in pathgrid.connections.iter().skip(offset).take(count) {
+            let Some(&to) = canonical.get(to as usize) else {
+                continue;
+            };
+            if to != from && !edges[from].contains(&to) {
+                edges[from].push(to);
+            }
+        }
+        offset += count;
+    }
+
+    let mut connected = vec![false; points.len()];
+    for (from, targets) in edges.iter().enumerate() {
+        if !targets.is_empty() {
+            connected[from] = true;
+        }
+        for &to in targets {
+            connected[to] = true;
+        }
+    }
+
+    let mut indices = HashMap::new();
+    let mut duplicates = 0;
+    let mut unconnected = 0;
+    for i in 0..points.len() {
+        if canonical[i] != i {
+            duplicates += 1;
+        } else if !connected[i] {
+            unconnected += 1;
+        } else {
+            indices.insert(i, indices.len() as u32);
+        }
+    }
+    if duplicates == 0 && unconnected == 0 {
+        return (0, 0);
+    }
+
+    let mut new_points = Vec::with_capacity(indices.len());
+    let mut new_connections = Vec::with_capacity(pathgrid.connections.len());
+    for (i, point) in pathgrid.points.iter().enumerate() {
+        if !indices.contains_key(&i) {
+            continue;
+        }
+        let mut point = point.clone();
+        point.connection_count = edges[i].len() as _;
+        new_points.push(point);
+        new_connections.extend(edges[i].iter().map(|to| indices[to]));
+    }
+    pathgrid.data.point_count = new_points.len() as _;
+    pathgrid.points = new_points;
+    pathgrid.connections = new_connections;
+    (duplicates, unconnected)
+}
+
+pub fn fix_pathgrids(plugin: &mut Plugin) {
+    for pathgrid in plugin.objects_of_type_mut::<PathGrid>() {
+        if pathgrid.points.is_empty() {
+            continue;
+        }
+        let (duplicates, unconnected) = fix_pathgrid(pathgrid);
+        if duplicates > 0 || unconnected > 0 {
+            println!(
+                "PathGrid {} had {} duplicate nodes merged and {} unconnected nodes removed",
+                pathgrid.editor_id(),
+                duplicates,
+                unconnected
+            );
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use crate::context::{Context, Mode};
+use crate::{
+    context::{Context, Mode},
+    mwscript::Statement,
+};
 use clap::ArgMatches;
 use std::error::Error;
 use tes3::esp::{Cell, Dialogue, DialogueInfo, FixedString, Reference, TES3Object};
@@ -40,6 +43,15 @@ pub trait Handler<'a> {
     ) {
     }
 
+    fn on_script_ast(
+        &mut self,
+        context: &Context,
+        record: &TES3Object,
+        statements: &[Statement],
+        topic: &Dialogue,
+    ) {
+    }
+
     fn on_end(&mut self, context: &Context) {}
 }
 
@@ -52,27 +64,45 @@ impl Handlers<'_> {
         let mut handlers: Vec<Box<dyn Handler<'a> + 'a>> = vec![
             Box::new(crate::validators::books::BookValidator {}),
             Box::new(crate::validators::cells::CellValidator::new()),
+            Box::new(crate::validators::chains::ChainValidator::new()),
+            Box::new(crate::validators::choices::ChoiceValidator::new()),
+            Box::new(crate::validators::compile::CompileValidator::new(context)),
             Box::new(crate::validators::corpse::CorpseValidator {}),
             Box::new(crate::validators::duplicates::DuplicateRefValidator::new(
                 args,
             )),
             Box::new(crate::validators::doors::DoorValidator {}),
+            Box::new(crate::validators::filters::FilterValidator::new()),
+            Box::new(crate::validators::identifiers::IdentifierValidator::new()),
+            Box::new(crate::validators::journals::JournalValidator::new()),
             Box::new(crate::validators::keys::KeyValidator::new()),
+            Box::new(crate::validators::lengths::LengthValidator {}),
             Box::new(crate::validators::leveled::LeveledValidator::new()),
-            Box::new(crate::validators::dialogue::DialogueValidator::new()?),
+            Box::new(crate::validators::dialogue::DialogueValidator::new(
+                context,
+            )?),
+            Box::new(crate::validators::macros::MacroValidator::new()),
             Box::new(crate::validators::magic::MagicValidator::new()),
             Box::new(crate::validators::missing::FieldValidator {}),
             Box::new(crate::validators::npc::NpcValidator::new()?),
-            Box::new(crate::validators::orphans::OrphanValidator::new()?),
+            Box::new(crate::validators::orphans::OrphanValidator::new()),
             Box::new(crate::validators::persistent::PersistentValidator::new()),
             Box::new(crate::validators::scripts::ScriptValidator::new(context)?),
             Box::new(crate::validators::services::ServiceValidator::new()),
+            Box::new(crate::validators::shadowing::ShadowValidator::new()),
             Box::new(crate::validators::soundgens::SoundGenValidator::new()),
             Box::new(crate::validators::supplies::SupplyChestValidator::new()),
             Box::new(crate::validators::todo::ToDoValidator::new()?),
             Box::new(crate::validators::travel::TravelValidator::new()),
-            Box::new(crate::validators::unicode::UnicodeValidator::new()?),
+            Box::new(crate::validators::unicode::UnicodeValidator::new(
+                context, args,
+            )?),
         ];
+        if args.get_flag("spellcheck") {
+            handlers.push(Box::new(crate::validators::spelling::SpellValidator::new(
+                args,
+            )?));
+        }
         if context.mode == Mode::PT || context.mode == Mode::TR {
             handlers.push(Box::new(crate::validators::classes::ClassValidator::new()));
         }
@@ -144,6 +174,18 @@ impl<'a> Handler<'a> for Handlers<'a> {
         }
     }
 
+    fn on_script_ast(
+        &mut self,
+        context: &Context,
+        record: &TES3Object,
+        statements: &[Statement],
+        topic: &Dialogue,
+    ) {
+        for handler in &mut self.handlers {
+            handler.on_script_ast(context, record, statements, topic);
+        }
+    }
+
     fn on_end(&mut self, context: &Context) {
         for handler in &mut self.handlers {
             handler.on_end(context);
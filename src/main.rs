@@ -1,15 +1,22 @@
 use clap::{crate_version, Arg, ArgGroup, ArgMatches, Command};
-use context::{Context, Mode};
+use context::{Context, Engine, Mode};
+use encoding::Encoding;
 use extended::ExtendedValidator;
-use oob::fix_oob;
-use std::{error::Error, path::Path};
+use fixes::{fix_books, fix_corpses, fix_duplicates, fix_keys, fix_pathgrids, get_master_miscs};
+use oob::{fix_oob, get_master_cells};
+use std::{collections::HashMap, error::Error, path::Path};
+use strings::{export_strings, import_strings};
 use tes3::esp::Plugin;
-use validators::Validator;
+use validators::{duplicates::get_threshold, Validator};
 
 mod context;
+mod encoding;
 mod extended;
+mod fixes;
 mod handlers;
+mod mwscript;
 mod oob;
+mod strings;
 mod util;
 mod validators;
 
@@ -24,8 +31,68 @@ fn main() -> Result<(), Box<dyn Error>> {
                 .value_name("output file")
                 .help(
                     "Move references that should belong to another cell to that cell \
+                and output a new file. Combine with --extended to use exterior cells \
+                from master files. Warning: overwrites the output file!",
+                ),
+            Arg::new("oobcreatecells")
+                .num_args(0)
+                .long("create-missing-cells")
+                .help(
+                    "Allow --fix-out-of-bounds to move references any distance \
+                and to create exterior cells that are not in the file.",
+                )
+                .requires("ooboutput"),
+            Arg::new("duplicateoutput")
+                .long("fix-duplicates")
+                .value_name("output file")
+                .help(
+                    "Remove duplicate references that are not persistent or used in scripts \
+                and output a new file. Warning: overwrites the output file!",
+                ),
+            Arg::new("corpseoutput")
+                .long("fix-corpses")
+                .value_name("output file")
+                .help(
+                    "Set corpse persists on dead NPCs and creatures \
+                and output a new file. Warning: overwrites the output file!",
+                ),
+            Arg::new("bookoutput")
+                .long("fix-books")
+                .value_name("output file")
+                .help(
+                    "Fix IMG SRC paths, invalid tags, unclosed tags, and invisible text in books \
+                and output a new file. Warning: overwrites the output file!",
+                ),
+            Arg::new("pathgridoutput")
+                .long("fix-path-grids")
+                .value_name("output file")
+                .help(
+                    "Merge duplicate path grid nodes and remove unconnected ones \
                 and output a new file. Warning: overwrites the output file!",
                 ),
+            Arg::new("keyoutput")
+                .long("fix-keys")
+                .value_name("output file")
+                .help(
+                    "Set the key flag on misc items that are used as keys or have key in their ID \
+                and output a new file. Combine with --extended to also override keys \
+                from master files. Warning: overwrites the output file!",
+                ),
+            Arg::new("exportstrings")
+                .long("export-strings")
+                .value_name("output file")
+                .help(
+                    "Write names, descriptions, book and dialogue text, and MessageBox and Choice \
+                strings to a .po or .csv file for translation. Warning: overwrites the output file!",
+                ),
+            Arg::new("importstrings")
+                .long("import-strings")
+                .num_args(2)
+                .value_names(["translation file", "output file"])
+                .help(
+                    "Replace strings with the translations in a .po or .csv file created by \
+                --export-strings and output a new file. Warning: overwrites the output file!",
+                ),
             Arg::new("extended")
                 .num_args(0)
                 .long("extended")
@@ -59,6 +126,45 @@ fn main() -> Result<(), Box<dyn Error>> {
                     "Squared distance at which two objects with the same id, \
                 scale, and orientation are considered duplicates.",
                 ),
+            Arg::new("engine")
+                .long("engine")
+                .default_value("vanilla")
+                .value_parser(["vanilla", "mcp", "openmw", "mwse"])
+                .help("Report script functions that are not available in this engine."),
+            Arg::new("encoding")
+                .long("encoding")
+                .default_value("english")
+                .value_parser(["english", "win1250", "win1251", "win1252"])
+                .help(
+                    "The language group of the plugin's text. Use win1250 for Central European, \
+                win1251 for Cyrillic, and win1252 for Western European languages other than English.",
+                ),
+            Arg::new("french")
+                .num_args(0)
+                .long("french")
+                .help(
+                    "The plugin's text is French. Allows spaces before colons, semicolons, \
+                and question marks, and non-breaking spaces.",
+                ),
+            Arg::new("spellcheck")
+                .num_args(0)
+                .long("spellcheck")
+                .help("Report unknown words in names, descriptions, books, and dialogue."),
+            Arg::new("dictionary")
+                .long("dictionary")
+                .value_name("file")
+                .help(
+                    "Additional words for --spellcheck to accept, such as proper nouns. \
+                One or more words per line, lines starting with # are ignored.",
+                )
+                .requires("spellcheck"),
+            Arg::new("terms")
+                .long("terms")
+                .value_name("file")
+                .help(
+                    "Additional terminology rules in the same JSON format as the built-in ones. \
+                Rules with the same pattern as a built-in rule replace it.",
+                ),
             Arg::new("mode")
                 .required(true)
                 .value_parser(["PT", "TD", "TR", "Vanilla"]),
@@ -68,7 +174,9 @@ fn main() -> Result<(), Box<dyn Error>> {
                 .help("C:/path/to/plugin.esp"),
         ])
         .groups([
-            ArgGroup::new("g_validator").args(["duplicatethreshold"]),
+            ArgGroup::new("g_validator")
+                .args(["duplicatethreshold", "french", "spellcheck", "dictionary", "terms"])
+                .multiple(true),
             ArgGroup::new("g_extended")
                 .args(["extended", "names"])
                 .conflicts_with("g_validator"),
@@ -76,27 +184,78 @@ fn main() -> Result<(), Box<dyn Error>> {
                 .arg("dontautoload")
                 .requires("g_extended"),
             ArgGroup::new("g_oob")
-                .arg("ooboutput")
-                .conflicts_with_all(["g_validator", "g_extended"]),
+                .args(["ooboutput", "oobcreatecells"])
+                .multiple(true)
+                .conflicts_with_all(["g_validator", "names"]),
+            ArgGroup::new("g_fixes")
+                .args([
+                    "duplicateoutput",
+                    "corpseoutput",
+                    "bookoutput",
+                    "pathgridoutput",
+                ])
+                .conflicts_with_all(["g_extended", "g_oob"]),
+            ArgGroup::new("g_keys")
+                .arg("keyoutput")
+                .conflicts_with_all(["g_validator", "names", "g_oob", "g_fixes"]),
+            ArgGroup::new("g_strings")
+                .args(["exportstrings", "importstrings"])
+                .conflicts_with_all(["g_validator", "g_extended", "g_oob", "g_fixes", "g_keys"]),
         ])
         .version(crate_version!())
         .get_matches();
     let mut paths = args.get_many::<String>("path").unwrap();
 
     if args.get_flag("extended") || args.get_flag("names") {
+        if let Some(output) = args.get_one::<String>("ooboutput") {
+            return run_oob_fixes(paths.collect(), output, &args);
+        }
+        if let Some(output) = args.get_one::<String>("keyoutput") {
+            return run_key_fixes(paths.collect(), output, &args);
+        }
         return Ok(run_extended(paths.collect(), &args)?);
     }
     if paths.clone().count() > 1 {
         Err("Multiple paths are only allowed for --extended and --names")?;
     }
     if let Some(output) = args.get_one::<String>("ooboutput") {
-        return run_oob_fixes(paths.next().unwrap(), output);
+        return run_oob_fixes(paths.collect(), output, &args);
+    }
+    if let Some(output) = args.get_one::<String>("duplicateoutput") {
+        return run_duplicate_fixes(paths.next().unwrap(), output, &args);
+    }
+    if let Some(output) = args.get_one::<String>("keyoutput") {
+        return run_key_fixes(paths.collect(), output, &args);
+    }
+    if let Some(output) = args.get_one::<String>("corpseoutput") {
+        return run_corpse_fixes(paths.next().unwrap(), output);
+    }
+    if let Some(output) = args.get_one::<String>("bookoutput") {
+        return run_book_fixes(paths.next().unwrap(), output);
+    }
+    if let Some(output) = args.get_one::<String>("pathgridoutput") {
+        return run_pathgrid_fixes(paths.next().unwrap(), output);
+    }
+    let encoding = args
+        .get_one::<String>("encoding")
+        .map_or(Encoding::English, Encoding::from);
+    if let Some(output) = args.get_one::<String>("exportstrings") {
+        return run_string_export(paths.next().unwrap(), output, encoding);
+    }
+    if let Some(mut files) = args.get_many::<String>("importstrings") {
+        let translation = files.next().unwrap();
+        let output = files.next().unwrap();
+        return run_string_import(paths.next().unwrap(), translation, output, encoding);
     }
     let mode = args
         .get_one::<String>("mode")
         .map_or(Mode::None, Mode::from);
 
-    let context = Context::new(mode);
+    let engine = args
+        .get_one::<String>("engine")
+        .map_or(Engine::Vanilla, Engine::from);
+    let french = args.get_flag("french");
+    let context = Context::new(mode, engine, encoding, french);
     validate(paths.next().unwrap(), context, &args)
 }
 
@@ -121,6 +280,23 @@ fn run_extended(paths: Vec<&String>, args: &ArgMatches) -> Result<(), String> {
     let mut validator = ExtendedValidator::new(args);
     let (plugin_path, master_paths) = paths.split_last().unwrap();
     let plugin = load_plugin(plugin_path)?;
+    load_masters(&plugin, plugin_path, master_paths, args, |master, file| {
+        validator.validate(&master.objects, file, false);
+    })?;
+    validator.validate(&plugin.objects, plugin_path, true);
+    Ok(())
+}
+
+fn load_masters<F>(
+    plugin: &Plugin,
+    plugin_path: &str,
+    master_paths: &[&String],
+    args: &ArgMatches,
+    mut on_master: F,
+) -> Result<(), String>
+where
+    F: FnMut(&Plugin, &str),
+{
     let mut auto_discovered = Vec::new();
     let autoload = !args.get_flag("dontautoload");
     if autoload {
@@ -131,12 +307,12 @@ fn run_extended(paths: Vec<&String>, args: &ArgMatches) -> Result<(), String> {
         }
     }
     for master_path in master_paths {
-        let path: &Path = plugin_path.as_ref();
+        let path: &Path = master_path.as_ref();
         let master = load_plugin(master_path)?;
         if autoload {
             auto_discovered.retain_mut(|p| !path.file_name().unwrap().eq_ignore_ascii_case(p));
         }
-        validator.validate(&master.objects, master_path, false);
+        on_master(&master, master_path);
     }
     if !auto_discovered.is_empty() {
         let path: &Path = plugin_path.as_ref();
@@ -145,16 +321,92 @@ fn run_extended(paths: Vec<&String>, args: &ArgMatches) -> Result<(), String> {
             let discovered_path = parent.join(name);
             let master = load_plugin(discovered_path.as_path())?;
             let file = discovered_path.to_str().unwrap_or("<funky path>");
-            validator.validate(&master.objects, file, false);
+            on_master(&master, file);
         }
     }
-    validator.validate(&plugin.objects, plugin_path, true);
     Ok(())
 }
 
-fn run_oob_fixes(input: &str, output: &str) -> Result<(), Box<dyn Error>> {
+fn run_oob_fixes(
+    paths: Vec<&String>,
+    output: &str,
+    args: &ArgMatches,
+) -> Result<(), Box<dyn Error>> {
+    let (input, master_paths) = paths.split_last().unwrap();
+    let mut plugin = load_plugin(input)?;
+    let mut master_cells = HashMap::new();
+    if args.get_flag("extended") {
+        load_masters(&plugin, input, master_paths, args, |master, _| {
+            get_master_cells(master, &mut master_cells);
+        })?;
+    }
+    fix_oob(&mut plugin, args.get_flag("oobcreatecells"), &master_cells);
+    plugin.save_path(output)?;
+    Ok(())
+}
+
+fn run_duplicate_fixes(input: &str, output: &str, args: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let mut plugin = load_plugin(input)?;
+    fix_duplicates(&mut plugin, get_threshold(args));
+    plugin.save_path(output)?;
+    Ok(())
+}
+
+fn run_corpse_fixes(input: &str, output: &str) -> Result<(), Box<dyn Error>> {
+    let mut plugin = load_plugin(input)?;
+    fix_corpses(&mut plugin);
+    plugin.save_path(output)?;
+    Ok(())
+}
+
+fn run_key_fixes(
+    paths: Vec<&String>,
+    output: &str,
+    args: &ArgMatches,
+) -> Result<(), Box<dyn Error>> {
+    let (input, master_paths) = paths.split_last().unwrap();
+    let mut plugin = load_plugin(input)?;
+    let mut master_miscs = HashMap::new();
+    if args.get_flag("extended") {
+        load_masters(&plugin, input, master_paths, args, |master, _| {
+            get_master_miscs(master, &mut master_miscs);
+        })?;
+    }
+    let mode = args
+        .get_one::<String>("mode")
+        .map_or(Mode::None, Mode::from);
+    fix_keys(&mut plugin, mode != Mode::TD, &master_miscs);
+    plugin.save_path(output)?;
+    Ok(())
+}
+
+fn run_book_fixes(input: &str, output: &str) -> Result<(), Box<dyn Error>> {
+    let mut plugin = load_plugin(input)?;
+    fix_books(&mut plugin);
+    plugin.save_path(output)?;
+    Ok(())
+}
+
+fn run_pathgrid_fixes(input: &str, output: &str) -> Result<(), Box<dyn Error>> {
+    let mut plugin = load_plugin(input)?;
+    fix_pathgrids(&mut plugin);
+    plugin.save_path(output)?;
+    Ok(())
+}
+
+fn run_string_export(input: &str, output: &str, encoding: Encoding) -> Result<(), Box<dyn Error>> {
+    let mut plugin = load_plugin(input)?;
+    export_strings(&mut plugin, output, encoding)
+}
+
+fn run_string_import(
+    input: &str,
+    translation: &str,
+    output: &str,
+    encoding: Encoding,
+) -> Result<(), Box<dyn Error>> {
     let mut plugin = load_plugin(input)?;
-    fix_oob(&mut plugin);
+    import_strings(&mut plugin, translation, encoding)?;
     plugin.save_path(output)?;
     Ok(())
 }
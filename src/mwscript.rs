@@ -0,0 +1,167 @@
+mod lexer;
+mod parser;
+
+use std::{collections::HashMap, sync::OnceLock};
+
+use crate::context::Engine;
+use codegen::get_function_signatures;
+
+pub use lexer::{find_strings, shows_strings, Operator};
+pub use parser::parse;
+
+/// The minimum and maximum number of arguments a function accepts
+pub type Signature = (usize, Option<usize>);
+
+/// Returns the functions an engine supports in addition to those supported by Morrowind.exe
+pub fn get_extensions(engine: Engine) -> HashMap<&'static str, Signature> {
+    match engine {
+        // The Morrowind Code Patch fixes existing functions but does not add any new ones
+        Engine::Vanilla | Engine::Mcp => HashMap::new(),
+        Engine::OpenMw => get_function_signatures!("openmw"),
+        Engine::Mwse => get_function_signatures!("mwse"),
+    }
+}
+
+pub fn get_functions(engine: Engine) -> HashMap<&'static str, Signature> {
+    let mut functions = get_function_signatures!();
+    functions.extend(get_extensions(engine));
+    functions
+}
+
+/// Returns every function supported by any engine, accepting the widest signature
+fn get_all_functions() -> &'static HashMap<&'static str, Signature> {
+    static FUNCTIONS: OnceLock<HashMap<&'static str, Signature>> = OnceLock::new();
+    FUNCTIONS.get_or_init(|| {
+        let mut functions = get_function_signatures!();
+        for engine in Engine::ALL {
+            for (name, (min, max)) in get_extensions(engine) {
+                functions
+                    .entry(name)
+                    .and_modify(|(old_min, old_max)| {
+                        *old_min = min.min(*old_min);
+                        *old_max = max.zip(*old_max).map(|(a, b)| a.max(b));
+                    })
+                    .or_insert((min, max));
+            }
+        }
+        functions
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VariableType {
+    Short,
+    Long,
+    Float,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Variable {
+    pub reference: Option<String>,
+    pub name: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Call {
+    pub reference: Option<String>,
+    pub function: String,
+    pub arguments: Vec<Expression>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expression {
+    Number(String),
+    String(String),
+    Variable(Variable),
+    Call(Call),
+    Negate(Box<Expression>),
+    Binary(Box<Expression>, Operator, Box<Expression>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum StatementKind {
+    Begin(String),
+    End,
+    Declaration(VariableType, String),
+    Set(Variable, Expression),
+    If(Expression),
+    ElseIf(Expression),
+    Else,
+    EndIf,
+    While(Expression),
+    EndWhile,
+    Return,
+    Call(Call),
+    Invalid(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Statement {
+    pub line: usize,
+    pub kind: StatementKind,
+}
+
+impl Call {
+    pub fn is(&self, function: &str) -> bool {
+        self.function.eq_ignore_ascii_case(function)
+    }
+
+    pub fn get_id(&self, index: usize) -> Option<&str> {
+        self.arguments.get(index).and_then(Expression::as_id)
+    }
+
+    fn for_each_call<'a>(&'a self, f: &mut impl FnMut(&'a Call)) {
+        f(self);
+        for argument in &self.arguments {
+            argument.for_each_call(f);
+        }
+    }
+}
+
+impl Expression {
+    /// Returns the text of a bare or quoted identifier
+    pub fn as_id(&self) -> Option<&str> {
+        match self {
+            Expression::String(id) => Some(id),
+            Expression::Variable(Variable {
+                reference: None,
+                name,
+            }) => Some(name),
+            _ => None,
+        }
+    }
+
+    pub fn as_integer(&self) -> Option<i32> {
+        match self {
+            Expression::Number(number) => number.parse().ok(),
+            Expression::Negate(value) => value.as_integer().map(|i| -i),
+            _ => None,
+        }
+    }
+
+    pub fn for_each_call<'a>(&'a self, f: &mut impl FnMut(&'a Call)) {
+        match self {
+            Expression::Call(call) => call.for_each_call(f),
+            Expression::Negate(value) => value.for_each_call(f),
+            Expression::Binary(left, _, right) => {
+                left.for_each_call(f);
+                right.for_each_call(f);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Statement {
+    /// Calls f for every function call in this statement, including those nested in expressions
+    pub fn for_each_call<'a>(&'a self, f: &mut impl FnMut(&'a Call)) {
+        match &self.kind {
+            StatementKind::Set(_, value)
+            | StatementKind::If(value)
+            | StatementKind::ElseIf(value)
+            | StatementKind::While(value) => value.for_each_call(f),
+            StatementKind::Call(call) => call.for_each_call(f),
+            _ => {}
+        }
+    }
+}
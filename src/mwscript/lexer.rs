@@ -0,0 +1,320 @@
+use std::{fmt, iter::Peekable, ops::Range, str::CharIndices};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Operator {
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Token {
+    Name(String),
+    String(String),
+    Number(String),
+    Operator(Operator),
+    Arrow,
+    Dot,
+    LeftParen,
+    RightParen,
+    Other(char),
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '\'' || c == '`'
+}
+
+struct Lexer<'a> {
+    line: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+    tokens: Vec<Token>,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(line: &'a str) -> Self {
+        Self {
+            line,
+            chars: line.char_indices().peekable(),
+            tokens: Vec::new(),
+        }
+    }
+
+    fn next_is(&mut self, expected: char) -> bool {
+        if self.chars.peek().is_some_and(|(_, c)| *c == expected) {
+            self.chars.next();
+            return true;
+        }
+        false
+    }
+
+    fn lex(mut self) -> Vec<Token> {
+        while let Some((start, c)) = self.chars.next() {
+            let token = match c {
+                ';' => break,
+                '"' => self.string(start),
+                '(' => Token::LeftParen,
+                ')' => Token::RightParen,
+                '+' => Token::Operator(Operator::Add),
+                '*' => Token::Operator(Operator::Multiply),
+                '/' => Token::Operator(Operator::Divide),
+                '-' => {
+                    if self.next_is('>') {
+                        Token::Arrow
+                    } else {
+                        Token::Operator(Operator::Subtract)
+                    }
+                }
+                '=' => {
+                    // The original compiler accepts a single = as a comparison
+                    self.next_is('=');
+                    Token::Operator(Operator::Equal)
+                }
+                '!' => {
+                    if self.next_is('=') {
+                        Token::Operator(Operator::NotEqual)
+                    } else {
+                        Token::Other(c)
+                    }
+                }
+                '<' => {
+                    if self.next_is('=') {
+                        Token::Operator(Operator::LessEqual)
+                    } else {
+                        Token::Operator(Operator::Less)
+                    }
+                }
+                '>' => {
+                    if self.next_is('=') {
+                        Token::Operator(Operator::GreaterEqual)
+                    } else {
+                        Token::Operator(Operator::Greater)
+                    }
+                }
+                '.' => {
+                    let after_value = matches!(
+                        self.tokens.last(),
+                        Some(Token::Name(_) | Token::String(_) | Token::RightParen)
+                    );
+                    if !after_value && self.chars.peek().is_some_and(|(_, c)| c.is_ascii_digit()) {
+                        self.word(start)
+                    } else {
+                        Token::Dot
+                    }
+                }
+                c if c.is_whitespace() || c == ',' => continue,
+                c if is_name_char(c) => self.word(start),
+                c => Token::Other(c),
+            };
+            self.tokens.push(token);
+        }
+        self.tokens
+    }
+
+    fn string(&mut self, start: usize) -> Token {
+        let mut end = self.line.len();
+        for (i, c) in self.chars.by_ref() {
+            if c == '"' {
+                end = i;
+                break;
+            }
+        }
+        Token::String(self.line[start + 1..end].to_string())
+    }
+
+    fn word(&mut self, start: usize) -> Token {
+        let mut end = self.line.len();
+        let mut number = self.line[start..].starts_with(|c: char| c.is_ascii_digit() || c == '.');
+        while let Some(&(i, c)) = self.chars.peek() {
+            if c.is_ascii_digit() || c == '.' && number {
+                self.chars.next();
+            } else if is_name_char(c) {
+                number = false;
+                self.chars.next();
+            } else if c == '-' && !number {
+                // Allow hyphenated IDs like ex-foo, subtraction needs spaces like x - 1
+                let mut lookahead = self.chars.clone();
+                lookahead.next();
+                if !lookahead.peek().is_some_and(|(_, c)| is_name_char(*c)) {
+                    end = i;
+                    break;
+                }
+                self.chars.next();
+            } else {
+                end = i;
+                break;
+            }
+        }
+        let word = &self.line[start..end];
+        if number {
+            Token::Number(word.to_string())
+        } else {
+            Token::Name(word.to_string())
+        }
+    }
+}
+
+pub fn tokenize(line: &str) -> Vec<Token> {
+    Lexer::new(line).lex()
+}
+
+/// Returns true if the line calls a function that shows its string arguments to the player
+pub fn shows_strings(line: &str) -> bool {
+    for token in tokenize(line) {
+        match token {
+            Token::Name(name)
+                if name.eq_ignore_ascii_case("messagebox")
+                    || name.eq_ignore_ascii_case("choice") =>
+            {
+                return true;
+            }
+            Token::String(_) => return false,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Returns the ranges of the quoted strings in a script line, excluding the quotes
+pub fn find_strings(line: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut start = None;
+    for (i, c) in line.char_indices() {
+        match (c, start) {
+            ('"', Some(s)) => {
+                ranges.push(s..i);
+                start = None;
+            }
+            ('"', None) => start = Some(i + 1),
+            (';', None) => break,
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        ranges.push(s..line.len());
+    }
+    ranges
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self {
+            Operator::Equal => "==",
+            Operator::NotEqual => "!=",
+            Operator::Less => "<",
+            Operator::LessEqual => "<=",
+            Operator::Greater => ">",
+            Operator::GreaterEqual => ">=",
+            Operator::Add => "+",
+            Operator::Subtract => "-",
+            Operator::Multiply => "*",
+            Operator::Divide => "/",
+        };
+        f.write_str(symbol)
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Name(name) => f.write_str(name),
+            Token::String(string) => write!(f, "\"{}\"", string),
+            Token::Number(number) => f.write_str(number),
+            Token::Operator(operator) => operator.fmt(f),
+            Token::Arrow => f.write_str("->"),
+            Token::Dot => f.write_str("."),
+            Token::LeftParen => f.write_str("("),
+            Token::RightParen => f.write_str(")"),
+            Token::Other(c) => write!(f, "{}", c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name(name: &str) -> Token {
+        Token::Name(name.to_string())
+    }
+
+    #[test]
+    fn arrow() {
+        assert_eq!(
+            tokenize("player->AddItem gold_001 10"),
+            [
+                name("player"),
+                Token::Arrow,
+                name("AddItem"),
+                name("gold_001"),
+                Token::Number("10".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn quoted_id_with_spaces() {
+        assert_eq!(
+            tokenize("\"some npc\"->Disable ; comment"),
+            [
+                Token::String("some npc".to_string()),
+                Token::Arrow,
+                name("Disable"),
+            ]
+        );
+    }
+
+    #[test]
+    fn negative_number() {
+        assert_eq!(
+            tokenize("ModDisposition -10"),
+            [
+                name("ModDisposition"),
+                Token::Operator(Operator::Subtract),
+                Token::Number("10".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn hyphenated_id() {
+        assert_eq!(
+            tokenize("AddItem ex-foo 1"),
+            [
+                name("AddItem"),
+                name("ex-foo"),
+                Token::Number("1".to_string()),
+            ]
+        );
+        assert_eq!(
+            tokenize("ex-foo->Disable"),
+            [name("ex-foo"), Token::Arrow, name("Disable")]
+        );
+    }
+
+    #[test]
+    fn subtraction() {
+        assert_eq!(
+            tokenize("x - 1"),
+            [
+                name("x"),
+                Token::Operator(Operator::Subtract),
+                Token::Number("1".to_string()),
+            ]
+        );
+        assert_eq!(
+            tokenize("2-1"),
+            [
+                Token::Number("2".to_string()),
+                Token::Operator(Operator::Subtract),
+                Token::Number("1".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,435 @@
+use std::collections::HashMap;
+
+use super::{
+    get_all_functions,
+    lexer::{tokenize, Operator, Token},
+    Call, Expression, Signature, Statement, StatementKind, Variable, VariableType,
+};
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    functions: &'static HashMap<&'static str, Signature>,
+}
+
+fn precedence(operator: Operator) -> u8 {
+    match operator {
+        Operator::Multiply | Operator::Divide => 2,
+        Operator::Add | Operator::Subtract => 1,
+        _ => 0,
+    }
+}
+
+fn is_argument_start(token: Option<&Token>) -> bool {
+    matches!(
+        token,
+        Some(Token::Name(_) | Token::String(_) | Token::Number(_))
+    )
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens.get(self.position + offset)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn is_end(&self) -> bool {
+        self.position >= self.tokens.len()
+    }
+
+    fn is_function(&self, name: &str) -> bool {
+        self.functions
+            .contains_key(name.to_ascii_lowercase().as_str())
+    }
+
+    fn max_arguments(&self, function: &str) -> Option<usize> {
+        self.functions
+            .get(function.to_ascii_lowercase().as_str())
+            .and_then(|(_, max)| *max)
+    }
+
+    fn unexpected(&self) -> String {
+        match self.peek() {
+            Some(token) => format!("Unexpected {}", token),
+            None => "Unexpected end of line".to_string(),
+        }
+    }
+
+    fn id(&mut self) -> Result<String, String> {
+        match self.peek() {
+            Some(Token::Name(id) | Token::String(id)) => {
+                let id = id.clone();
+                self.position += 1;
+                Ok(id)
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn statement(&mut self) -> Result<StatementKind, String> {
+        let keyword = match self.peek() {
+            Some(Token::Name(name)) if self.peek_at(1) != Some(&Token::Arrow) => {
+                name.to_ascii_lowercase()
+            }
+            _ => String::new(),
+        };
+        let var_type = match keyword.as_str() {
+            "short" => Some(VariableType::Short),
+            "long" => Some(VariableType::Long),
+            "float" => Some(VariableType::Float),
+            _ => None,
+        };
+        if let Some(var_type) = var_type {
+            self.next();
+            return Ok(StatementKind::Declaration(var_type, self.id()?));
+        }
+        let kind = match keyword.as_str() {
+            "begin" => {
+                self.next();
+                StatementKind::Begin(self.id()?)
+            }
+            "end" => StatementKind::End,
+            "else" => StatementKind::Else,
+            "endif" => StatementKind::EndIf,
+            "endwhile" => StatementKind::EndWhile,
+            "return" => StatementKind::Return,
+            "set" => {
+                self.next();
+                let variable = self.variable()?;
+                match self.next() {
+                    Some(Token::Name(to)) if to.eq_ignore_ascii_case("to") => {}
+                    _ => return Err("Expected to".to_string()),
+                }
+                return Ok(StatementKind::Set(variable, self.expression()?));
+            }
+            "if" => {
+                self.next();
+                return Ok(StatementKind::If(self.expression()?));
+            }
+            "elseif" => {
+                self.next();
+                return Ok(StatementKind::ElseIf(self.expression()?));
+            }
+            "while" => {
+                self.next();
+                return Ok(StatementKind::While(self.expression()?));
+            }
+            _ => {
+                let mut call = self.call()?;
+                let max = self.max_arguments(&call.function);
+                while !self.is_end() {
+                    // Once a known function has all its arguments, a name starts the next statement
+                    let full = max.is_some_and(|max| call.arguments.len() >= max);
+                    if full && matches!(self.peek(), Some(Token::Name(_))) {
+                        break;
+                    }
+                    call.arguments.push(self.argument()?);
+                }
+                return Ok(StatementKind::Call(call));
+            }
+        };
+        // Trailing tokens after block keywords are ignored by the compiler
+        self.position = self.tokens.len();
+        Ok(kind)
+    }
+
+    fn variable(&mut self) -> Result<Variable, String> {
+        let name = self.id()?;
+        if self.peek() == Some(&Token::Dot) {
+            self.next();
+            return Ok(Variable {
+                reference: Some(name),
+                name: self.id()?,
+            });
+        }
+        Ok(Variable {
+            reference: None,
+            name,
+        })
+    }
+
+    fn call(&mut self) -> Result<Call, String> {
+        let mut reference = None;
+        if self.peek_at(1) == Some(&Token::Arrow) {
+            reference = Some(self.id()?);
+            self.next();
+        }
+        match self.next() {
+            Some(Token::Name(function)) => Ok(Call {
+                reference,
+                function,
+                arguments: Vec::new(),
+            }),
+            _ => {
+                self.position -= 1;
+                Err(self.unexpected())
+            }
+        }
+    }
+
+    fn argument(&mut self) -> Result<Expression, String> {
+        match self.peek() {
+            Some(Token::Number(number)) => {
+                let number = number.clone();
+                self.next();
+                Ok(Expression::Number(number))
+            }
+            Some(Token::String(_)) if self.peek_at(1) != Some(&Token::Dot) => {
+                Ok(Expression::String(self.id()?))
+            }
+            Some(Token::Name(_) | Token::String(_)) => Ok(Expression::Variable(self.variable()?)),
+            Some(Token::Operator(Operator::Subtract)) => {
+                self.next();
+                Ok(Expression::Negate(Box::new(self.argument()?)))
+            }
+            Some(Token::LeftParen) => self.parenthesized(),
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn parenthesized(&mut self) -> Result<Expression, String> {
+        self.next();
+        let expression = self.expression()?;
+        if self.next() != Some(Token::RightParen) {
+            self.position -= 1;
+            return Err(self.unexpected());
+        }
+        Ok(expression)
+    }
+
+    fn expression(&mut self) -> Result<Expression, String> {
+        self.binary(0)
+    }
+
+    fn binary(&mut self, level: u8) -> Result<Expression, String> {
+        if level > 2 {
+            return self.unary();
+        }
+        let mut left = self.binary(level + 1)?;
+        while let Some(&Token::Operator(operator)) = self.peek() {
+            if precedence(operator) != level {
+                break;
+            }
+            self.next();
+            let right = self.binary(level + 1)?;
+            left = Expression::Binary(Box::new(left), operator, Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expression, String> {
+        if self.peek() == Some(&Token::Operator(Operator::Subtract)) {
+            self.next();
+            return Ok(Expression::Negate(Box::new(self.unary()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expression, String> {
+        match self.peek() {
+            Some(Token::LeftParen) => self.parenthesized(),
+            Some(Token::Name(_) | Token::String(_)) if self.peek_at(1) == Some(&Token::Arrow) => {
+                self.call_expression()
+            }
+            Some(Token::Name(name))
+                if self.peek_at(1) != Some(&Token::Dot)
+                    && (self.is_function(name) || is_argument_start(self.peek_at(1))) =>
+            {
+                self.call_expression()
+            }
+            _ => self.argument(),
+        }
+    }
+
+    fn call_expression(&mut self) -> Result<Expression, String> {
+        let mut call = self.call()?;
+        let max = self.max_arguments(&call.function);
+        while is_argument_start(self.peek()) && max.is_none_or(|max| call.arguments.len() < max) {
+            call.arguments.push(self.argument()?);
+        }
+        Ok(Expression::Call(call))
+    }
+}
+
+/// Parses script source into statements, of which a line can contain several
+pub fn parse(text: &str) -> Vec<Statement> {
+    let mut statements = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let mut parser = Parser {
+            tokens: tokenize(line),
+            position: 0,
+            functions: get_all_functions(),
+        };
+        while !parser.is_end() {
+            match parser.statement() {
+                Ok(kind) => statements.push(Statement { line: i + 1, kind }),
+                Err(reason) => {
+                    statements.push(Statement {
+                        line: i + 1,
+                        kind: StatementKind::Invalid(reason),
+                    });
+                    break;
+                }
+            }
+        }
+    }
+    statements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(text: &str) -> Vec<StatementKind> {
+        parse(text).into_iter().map(|s| s.kind).collect()
+    }
+
+    fn number(number: &str) -> Expression {
+        Expression::Number(number.to_string())
+    }
+
+    fn variable(name: &str) -> Expression {
+        Expression::Variable(Variable {
+            reference: None,
+            name: name.to_string(),
+        })
+    }
+
+    fn call(reference: Option<&str>, function: &str, arguments: Vec<Expression>) -> Call {
+        Call {
+            reference: reference.map(str::to_string),
+            function: function.to_string(),
+            arguments,
+        }
+    }
+
+    #[test]
+    fn arrow_call() {
+        assert_eq!(
+            kinds("player->AddItem gold_001 10"),
+            [StatementKind::Call(call(
+                Some("player"),
+                "AddItem",
+                vec![variable("gold_001"), number("10")]
+            ))]
+        );
+    }
+
+    #[test]
+    fn quoted_id_with_spaces() {
+        assert_eq!(
+            kinds("\"some npc\"->Disable"),
+            [StatementKind::Call(call(
+                Some("some npc"),
+                "Disable",
+                vec![]
+            ))]
+        );
+        assert_eq!(
+            kinds("set \"some npc\".state to 1"),
+            [StatementKind::Set(
+                Variable {
+                    reference: Some("some npc".to_string()),
+                    name: "state".to_string(),
+                },
+                number("1")
+            )]
+        );
+    }
+
+    #[test]
+    fn negative_number() {
+        assert_eq!(
+            kinds("ModDisposition -10"),
+            [StatementKind::Call(call(
+                None,
+                "ModDisposition",
+                vec![Expression::Negate(Box::new(number("10")))]
+            ))]
+        );
+    }
+
+    #[test]
+    fn subtraction() {
+        assert_eq!(
+            kinds("set x to x - 1"),
+            [StatementKind::Set(
+                Variable {
+                    reference: None,
+                    name: "x".to_string(),
+                },
+                Expression::Binary(
+                    Box::new(variable("x")),
+                    Operator::Subtract,
+                    Box::new(number("1"))
+                )
+            )]
+        );
+    }
+
+    #[test]
+    fn zero_argument_function_in_condition() {
+        assert_eq!(
+            kinds("if ( OnActivate )"),
+            [StatementKind::If(Expression::Call(call(
+                None,
+                "OnActivate",
+                vec![]
+            )))]
+        );
+        assert_eq!(
+            kinds("if ( GetJournalIndex quest >= 10 )"),
+            [StatementKind::If(Expression::Binary(
+                Box::new(Expression::Call(call(
+                    None,
+                    "GetJournalIndex",
+                    vec![variable("quest")]
+                ))),
+                Operator::GreaterEqual,
+                Box::new(number("10"))
+            ))]
+        );
+    }
+
+    #[test]
+    fn multiple_statements_on_one_line() {
+        let statements = parse("if ( x == 1 ) set x to 2\nJournal quest 10 StopScript test");
+        let lines: Vec<_> = statements.iter().map(|s| s.line).collect();
+        assert_eq!(lines, [1, 1, 2, 2]);
+        assert_eq!(
+            statements[1].kind,
+            StatementKind::Set(
+                Variable {
+                    reference: None,
+                    name: "x".to_string(),
+                },
+                number("2")
+            )
+        );
+        assert_eq!(
+            statements[3].kind,
+            StatementKind::Call(call(None, "StopScript", vec![variable("test")]))
+        );
+    }
+
+    #[test]
+    fn too_many_arguments_stay_on_the_call() {
+        assert_eq!(
+            kinds("AddItem gold_001 10 5"),
+            [StatementKind::Call(call(
+                None,
+                "AddItem",
+                vec![variable("gold_001"), number("10"), number("5")]
+            ))]
+        );
+    }
+}
@@ -1,69 +1,177 @@
 use std::collections::HashMap;
 
-use tes3::esp::{Cell, Plugin};
+use tes3::esp::{Cell, CellData, CellFlags, Plugin, TES3Object};
 
 use crate::util::get_cell_grid;
 
-pub fn fix_oob(plugin: &mut Plugin) {
-    let mut exteriors: HashMap<_, _> = plugin
-        .objects_of_type_mut::<Cell>()
-        .filter_map(|cell| Some((cell.exterior_coords()?, cell)))
-        .collect();
+/// Creates an exterior cell, taking its region from an adjacent cell if there is one
+fn create_cell(
+    grid: (i32, i32),
+    exteriors: &HashMap<(i32, i32), &Cell>,
+    master_cells: &HashMap<(i32, i32), Cell>,
+) -> Cell {
+    let region = (-1..=1)
+        .flat_map(|dx| (-1..=1).map(move |dy| (grid.0 + dx, grid.1 + dy)))
+        .filter_map(|neighbor| {
+            exteriors
+                .get(&neighbor)
+                .copied()
+                .or_else(|| master_cells.get(&neighbor))
+        })
+        .find_map(|cell| cell.region.clone());
+    Cell {
+        data: CellData {
+            flags: CellFlags::default(),
+            grid,
+        },
+        region,
+        ..Default::default()
+    }
+}
+
+fn create_override(cell: &Cell) -> Cell {
+    Cell {
+        flags: cell.flags,
+        name: cell.name.clone(),
+        data: cell.data.clone(),
+        region: cell.region.clone(),
+        map_color: cell.map_color,
+        ..Default::default()
+    }
+}
 
+pub fn get_master_cells(master: &Plugin, cells: &mut HashMap<(i32, i32), Cell>) {
+    for cell in master.objects_of_type::<Cell>() {
+        if let Some(grid) = cell.exterior_coords() {
+            cells.insert(grid, create_override(cell));
+        }
+    }
+}
+
+pub fn fix_oob(plugin: &mut Plugin, create_cells: bool, master_cells: &HashMap<(i32, i32), Cell>) {
     let mut out_of_bounds = vec![];
+    let mut created = HashMap::new();
+    let mut skipped = 0;
 
-    for (grid, cell) in &exteriors {
-        for (key, reference) in &cell.references {
-            if reference.deleted == Some(true) {
-                continue;
-            }
+    {
+        let exteriors: HashMap<_, _> = plugin
+            .objects_of_type::<Cell>()
+            .filter_map(|cell| Some((cell.exterior_coords()?, cell)))
+            .collect();
 
-            let [x, y, _] = reference.translation;
-            let actual_grid = get_cell_grid(x as f64, y as f64);
-            let dx = (grid.0 - actual_grid.0).abs();
-            let dy = (grid.1 - actual_grid.1).abs();
+        for (grid, cell) in &exteriors {
+            for (key, reference) in &cell.references {
+                if reference.deleted == Some(true) {
+                    continue;
+                }
 
-            // In the correct cell
-            if dx == 0 && dy == 0 {
-                continue;
-            }
+                let [x, y, _] = reference.translation;
+                if !x.is_finite() || !y.is_finite() {
+                    println!(
+                        "Not moving {} {:?} from cell {:?} as its position is invalid",
+                        reference.id, key, grid
+                    );
+                    skipped += 1;
+                    continue;
+                }
+                let actual_grid = get_cell_grid(x as f64, y as f64);
+                let dx = (grid.0 - actual_grid.0).abs();
+                let dy = (grid.1 - actual_grid.1).abs();
 
-            // More than 1 cell away
-            if dx > 1 || dy > 1 {
-                println!(
-                    "Not moving {} {:?} from cell {:?} as cell {:?} is too far away",
-                    reference.id, key, grid, actual_grid
-                );
-                continue;
-            }
+                // In the correct cell
+                if dx == 0 && dy == 0 {
+                    continue;
+                }
+
+                // More than 1 cell away
+                if !create_cells && (dx > 1 || dy > 1) {
+                    println!(
+                        "Not moving {} {:?} from cell {:?} as cell {:?} is too far away",
+                        reference.id, key, grid, actual_grid
+                    );
+                    skipped += 1;
+                    continue;
+                }
+
+                // In an undefined cell
+                if !exteriors.contains_key(&actual_grid) {
+                    if let Some(master_cell) = master_cells.get(&actual_grid) {
+                        created
+                            .entry(actual_grid)
+                            .or_insert_with(|| master_cell.clone());
+                    } else if create_cells {
+                        created
+                            .entry(actual_grid)
+                            .or_insert_with(|| create_cell(actual_grid, &exteriors, master_cells));
+                    } else {
+                        println!(
+                            "Not moving {} {:?} from cell {:?} as cell {:?} is not in this file",
+                            reference.id, key, grid, actual_grid
+                        );
+                        skipped += 1;
+                        continue;
+                    }
+                }
 
-            // In an undefined cell
-            if !exteriors.contains_key(&actual_grid) {
                 println!(
-                    "Not moving {} {:?} from cell {:?} as cell {:?} is not in this file",
+                    "Moving {} {:?} from {:?} to {:?}",
                     reference.id, key, grid, actual_grid
                 );
-                continue;
+                out_of_bounds.push((*grid, actual_grid, *key));
             }
+        }
+    }
 
-            // In a neighboring cell
-            println!(
-                "Moving {} {:?} from {:?} to {:?}",
-                reference.id, key, grid, actual_grid
-            );
-            out_of_bounds.push((*grid, actual_grid, *key));
+    let created_count = created.len();
+    if !created.is_empty() {
+        let mut cells: Vec<_> = created.into_values().collect();
+        cells.sort_by_key(|cell| cell.data.grid);
+        for cell in &cells {
+            if master_cells.contains_key(&cell.data.grid) {
+                println!("Overriding master cell {:?}", cell.data.grid);
+            } else {
+                println!("Creating cell {:?}", cell.data.grid);
+            }
         }
+        let index = plugin
+            .objects
+            .iter()
+            .rposition(|object| matches!(object, TES3Object::Cell(_)))
+            .map_or(plugin.objects.len(), |i| i + 1);
+        plugin
+            .objects
+            .splice(index..index, cells.into_iter().map(TES3Object::Cell));
     }
 
-    out_of_bounds
-        .into_iter()
-        .try_for_each(|(old_grid, new_grid, key)| {
-            let old_cell = exteriors.get_mut(&old_grid)?;
-            let reference = old_cell.references.remove(&key)?;
+    let mut exteriors: HashMap<_, _> = plugin
+        .objects_of_type_mut::<Cell>()
+        .filter_map(|cell| Some((cell.exterior_coords()?, cell)))
+        .collect();
 
-            let new_cell = exteriors.get_mut(&new_grid)?;
+    let mut moved = 0;
+    for (old_grid, new_grid, key) in out_of_bounds {
+        if !exteriors.contains_key(&new_grid) {
+            println!(
+                "Not moving {:?} from cell {:?} as cell {:?} does not exist",
+                key, old_grid, new_grid
+            );
+            skipped += 1;
+            continue;
+        }
+        let Some(reference) = exteriors
+            .get_mut(&old_grid)
+            .and_then(|cell| cell.references.remove(&key))
+        else {
+            continue;
+        };
+        if let Some(new_cell) = exteriors.get_mut(&new_grid) {
             new_cell.references.insert(key, reference);
+            moved += 1;
+        }
+    }
 
-            Some(())
-        });
+    println!(
+        "Moved {} references, skipped {} references, created {} cells",
+        moved, skipped, created_count
+    );
 }
@@ -0,0 +1,340 @@
+use std::{collections::HashMap, error::Error, fs, path::Path};
+
+use crate::{
+    encoding::Encoding,
+    mwscript::{find_strings, shows_strings},
+};
+use tes3::esp::{EditorId, Plugin, TES3Object, TypeInfo};
+
+struct Translation {
+    text: String,
+    translation: String,
+}
+
+fn is_csv(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"))
+}
+
+fn visit_script<F>(prefix: &str, text: &mut String, f: &mut F)
+where
+    F: FnMut(String, &mut String),
+{
+    let mut count = 0;
+    let mut output = Vec::new();
+    for line in text.split('\n') {
+        if !shows_strings(line) {
+            output.push(line.to_string());
+            continue;
+        }
+        let mut replaced = String::with_capacity(line.len());
+        let mut last = 0;
+        for range in find_strings(line) {
+            count += 1;
+            let mut value = line[range.clone()].to_string();
+            f(format!("{}:string {}", prefix, count), &mut value);
+            replaced.push_str(&line[last..range.start]);
+            replaced.push_str(&value);
+            last = range.end;
+        }
+        replaced.push_str(&line[last..]);
+        output.push(replaced);
+    }
+    let output = output.join("\n");
+    if output != *text {
+        *text = output;
+    }
+}
+
+fn visit_field<F>(prefix: &str, field: &str, value: &mut String, f: &mut F)
+where
+    F: FnMut(String, &mut String),
+{
+    if !value.is_empty() {
+        f(format!("{}:{}", prefix, field), value);
+    }
+}
+
+/// Calls f for every player-facing string in the plugin with a key made up of the record type, ID, and field
+fn for_each_string<F>(plugin: &mut Plugin, mut f: F)
+where
+    F: FnMut(String, &mut String),
+{
+    let mut topic = String::new();
+    for record in &mut plugin.objects {
+        let prefix = format!("{}:{}", record.type_name(), record.editor_id());
+        let mut field =
+            |field: &str, value: &mut String| visit_field(&prefix, field, value, &mut f);
+        match record {
+            TES3Object::Activator(r) => field("name", &mut r.name),
+            TES3Object::Alchemy(r) => field("name", &mut r.name),
+            TES3Object::Apparatus(r) => field("name", &mut r.name),
+            TES3Object::Armor(r) => field("name", &mut r.name),
+            TES3Object::Birthsign(r) => {
+                field("name", &mut r.name);
+                field("description", &mut r.description);
+            }
+            TES3Object::Book(r) => {
+                field("name", &mut r.name);
+                field("text", &mut r.text);
+            }
+            TES3Object::Class(r) => {
+                field("name", &mut r.name);
+                field("description", &mut r.description);
+            }
+            TES3Object::Clothing(r) => field("name", &mut r.name),
+            TES3Object::Container(r) => field("name", &mut r.name),
+            TES3Object::Creature(r) => field("name", &mut r.name),
+            TES3Object::Dialogue(r) => r.id.clone_into(&mut topic),
+            TES3Object::DialogueInfo(r) => {
+                let prefix = format!("{}:{}:{}", r.type_name(), topic, r.id);
+                if !r.text.is_empty() {
+                    f(format!("{}:text", prefix), &mut r.text);
+                }
+                visit_script(&prefix, &mut r.script_text, &mut f);
+            }
+            TES3Object::Door(r) => field("name", &mut r.name),
+            TES3Object::Faction(r) => field("name", &mut r.name),
+            TES3Object::Ingredient(r) => field("name", &mut r.name),
+            TES3Object::Light(r) => field("name", &mut r.name),
+            TES3Object::Lockpick(r) => field("name", &mut r.name),
+            TES3Object::MagicEffect(r) => field("description", &mut r.description),
+            TES3Object::MiscItem(r) => field("name", &mut r.name),
+            TES3Object::Npc(r) => field("name", &mut r.name),
+            TES3Object::Probe(r) => field("name", &mut r.name),
+            TES3Object::Race(r) => {
+                field("name", &mut r.name);
+                field("description", &mut r.description);
+            }
+            TES3Object::Region(r) => field("name", &mut r.name),
+            TES3Object::RepairItem(r) => field("name", &mut r.name),
+            TES3Object::Script(r) => visit_script(&prefix, &mut r.text, &mut f),
+            TES3Object::Spell(r) => field("name", &mut r.name),
+            TES3Object::Weapon(r) => field("name", &mut r.name),
+            _ => {}
+        }
+    }
+}
+
+fn escape_po(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape_po(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some('t') => unescaped.push('\t'),
+            Some(c) => unescaped.push(c),
+            None => {}
+        }
+    }
+    unescaped
+}
+
+fn write_po(strings: &[(String, String)]) -> String {
+    let mut output =
+        String::from("msgid \"\"\nmsgstr \"\"\n\"Content-Type: text/plain; charset=UTF-8\\n\"\n");
+    for (key, text) in strings {
+        output.push_str(&format!(
+            "\nmsgctxt \"{}\"\nmsgid \"{}\"\nmsgstr \"\"\n",
+            escape_po(key),
+            escape_po(text)
+        ));
+    }
+    output
+}
+
+fn read_po(contents: &str) -> Result<HashMap<String, Translation>, String> {
+    let mut translations = HashMap::new();
+    let mut key = None;
+    let mut text = String::new();
+    let mut translation = String::new();
+    let mut current = None;
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        let (keyword, value) = match line.split_once(' ') {
+            Some((keyword, value)) if !keyword.starts_with('"') => (Some(keyword), value),
+            _ => (None, line),
+        };
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some(value) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) else {
+            return Err(format!("Invalid string on line {}", i + 1));
+        };
+        let value = unescape_po(value);
+        match keyword {
+            Some("msgctxt") => {
+                if let Some(key) = key.take() {
+                    translations.insert(key, Translation { text, translation });
+                }
+                key = Some(value);
+                text = String::new();
+                translation = String::new();
+                current = None;
+            }
+            Some("msgid") => {
+                text = value;
+                current = Some(false);
+            }
+            Some("msgstr") => {
+                translation = value;
+                current = Some(true);
+            }
+            Some(keyword) => return Err(format!("Unknown keyword {} on line {}", keyword, i + 1)),
+            None => match current {
+                Some(false) => text.push_str(&value),
+                Some(true) => translation.push_str(&value),
+                None => return Err(format!("Unexpected string on line {}", i + 1)),
+            },
+        }
+    }
+    if let Some(key) = key {
+        translations.insert(key, Translation { text, translation });
+    }
+    Ok(translations)
+}
+
+fn escape_csv(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "\"\""))
+}
+
+fn write_csv(strings: &[(String, String)]) -> String {
+    let mut output = String::from("key,text,translation\n");
+    for (key, text) in strings {
+        output.push_str(&format!("{},{},\"\"\n", escape_csv(key), escape_csv(text)));
+    }
+    output
+}
+
+fn read_csv(contents: &str) -> Result<HashMap<String, Translation>, String> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = contents.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    quoted = false;
+                }
+            }
+            '"' if field.is_empty() => quoted = true,
+            ',' if !quoted => row.push(std::mem::take(&mut field)),
+            '\r' if !quoted && chars.peek() == Some(&'\n') => {}
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    let mut translations = HashMap::new();
+    for (i, row) in rows.into_iter().enumerate().skip(1) {
+        let [key, text, translation] = <[String; 3]>::try_from(row)
+            .map_err(|row| format!("Expected 3 columns on row {}, found {}", i + 1, row.len()))?;
+        translations.insert(key, Translation { text, translation });
+    }
+    Ok(translations)
+}
+
+pub fn export_strings(
+    plugin: &mut Plugin,
+    output: &str,
+    encoding: Encoding,
+) -> Result<(), Box<dyn Error>> {
+    let mut strings = Vec::new();
+    for_each_string(plugin, |key, value| {
+        strings.push((key, encoding.decode(value).into_owned()))
+    });
+    let contents = if is_csv(output) {
+        write_csv(&strings)
+    } else {
+        write_po(&strings)
+    };
+    fs::write(output, contents)
+        .map_err(|e| format!("Failed to write strings to {} ({})", output, e))?;
+    Ok(())
+}
+
+pub fn import_strings(
+    plugin: &mut Plugin,
+    input: &str,
+    encoding: Encoding,
+) -> Result<(), Box<dyn Error>> {
+    let contents = fs::read_to_string(input)
+        .map_err(|e| format!("Failed to load strings from {} ({})", input, e))?;
+    let mut translations = if is_csv(input) {
+        read_csv(&contents)?
+    } else {
+        read_po(&contents)?
+    };
+    let mut scripts = Vec::new();
+    for_each_string(plugin, |key, value| {
+        let Some(entry) = translations.remove(&key) else {
+            return;
+        };
+        if entry.translation.is_empty() {
+            return;
+        }
+        if entry.text != encoding.decode(value) {
+            println!("String {} has changed since it was exported", key);
+            return;
+        }
+        match encoding.encode(&entry.translation) {
+            Ok(translation) => *value = translation,
+            Err(c) => {
+                println!(
+                    "String {} contains character {} which cannot be encoded",
+                    key, c
+                );
+                return;
+            }
+        }
+        // Script records are run from their compiled bytecode, which still contains the old text
+        if let Some(script) = key
+            .strip_prefix("Script:")
+            .and_then(|rest| rest.rsplit_once(':'))
+            .map(|(id, _)| id.to_string())
+        {
+            if !scripts.contains(&script) {
+                scripts.push(script);
+            }
+        }
+    });
+    for key in translations.keys() {
+        println!("String {} does not exist", key);
+    }
+    for id in scripts {
+        println!("Script {} needs to be recompiled", id);
+    }
+    Ok(())
+}
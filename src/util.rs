@@ -1,7 +1,7 @@
 use std::{collections::HashMap, hash::Hash};
 use tes3::esp::{
-    Book, Cell, CellFlags, Creature, Npc, NpcFlags, ObjectFlags, TES3Object, TravelDestination,
-    TypeInfo,
+    Book, Cell, CellFlags, Creature, MiscItem, MiscItemFlags, Npc, NpcFlags, ObjectFlags,
+    TES3Object, TravelDestination, TypeInfo,
 };
 
 pub const CELL_SIZE: f64 = 8192.;
@@ -97,6 +97,30 @@ pub fn is_persistent(record: &TES3Object) -> bool {
     }
 }
 
+pub fn get_script(record: &TES3Object) -> Option<&str> {
+    let script = match record {
+        TES3Object::Activator(r) => &r.script,
+        TES3Object::Alchemy(r) => &r.script,
+        TES3Object::Apparatus(r) => &r.script,
+        TES3Object::Armor(r) => &r.script,
+        TES3Object::Book(r) => &r.script,
+        TES3Object::Clothing(r) => &r.script,
+        TES3Object::Container(r) => &r.script,
+        TES3Object::Creature(r) => &r.script,
+        TES3Object::Door(r) => &r.script,
+        TES3Object::Ingredient(r) => &r.script,
+        TES3Object::Light(r) => &r.script,
+        TES3Object::Lockpick(r) => &r.script,
+        TES3Object::MiscItem(r) => &r.script,
+        TES3Object::Npc(r) => &r.script,
+        TES3Object::Probe(r) => &r.script,
+        TES3Object::RepairItem(r) => &r.script,
+        TES3Object::Weapon(r) => &r.script,
+        _ => return None,
+    };
+    Some(script)
+}
+
 pub fn get_cell_grid(x: f64, y: f64) -> (i32, i32) {
     (
         f64::floor(x / CELL_SIZE) as i32,
@@ -127,6 +151,11 @@ where
     }
 }
 
+/// Returns true if a faction field is set to something other than the engine's "no faction" value
+pub fn has_faction(faction: &str) -> bool {
+    !faction.is_empty() && faction != "FFFF"
+}
+
 pub fn is_autocalc(npc: &Npc) -> bool {
     npc.npc_flags.contains(NpcFlags::AUTO_CALCULATE)
 }
@@ -135,6 +164,10 @@ pub fn cannot_sleep(cell: &Cell) -> bool {
     cell.data.flags.contains(CellFlags::RESTING_IS_ILLEGAL)
 }
 
+pub fn is_key(misc: &MiscItem) -> bool {
+    misc.data.flags.contains(MiscItemFlags::KEY)
+}
+
 pub fn is_marker(book: &Book) -> bool {
     let mesh = &book.mesh;
     mesh.eq_ignore_ascii_case("tr\\tr_note_pin.nif")
@@ -1,14 +1,22 @@
 pub mod autocalc;
 pub mod books;
 pub mod cells;
+pub mod chains;
+pub mod choices;
 pub mod classes;
+pub mod compile;
 pub mod corpse;
 pub mod dialogue;
 pub mod doors;
 pub mod duplicates;
+pub mod filters;
+pub mod identifiers;
 pub mod ids;
+pub mod journals;
 pub mod keys;
+pub mod lengths;
 pub mod leveled;
+pub mod macros;
 pub mod magic;
 pub mod missing;
 pub mod npc;
@@ -16,7 +24,9 @@ pub mod orphans;
 pub mod persistent;
 pub mod scripts;
 pub mod services;
+pub mod shadowing;
 pub mod soundgens;
+pub mod spelling;
 pub mod supplies;
 pub mod todo;
 pub mod travel;
@@ -26,6 +36,7 @@ pub mod uniques;
 use crate::{
     context::Context,
     handlers::{Handler, Handlers},
+    mwscript,
 };
 use clap::ArgMatches;
 use std::error::Error;
@@ -163,5 +174,8 @@ impl<'a> Validator<'a> {
                 );
             }
         }
+        let statements = mwscript::parse(script_text);
+        self.handlers
+            .on_script_ast(&self.context, record, &statements, topic);
     }
 }
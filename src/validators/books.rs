@@ -98,7 +98,7 @@ impl Slice {
     }
 }
 
-const TAGS: [&str; 7] = ["div", "font", "br", "p", "img", "b", "deprecated"]; //ok, so maybe that last one isn't real
+pub const TAGS: [&str; 7] = ["div", "font", "br", "p", "img", "b", "deprecated"]; //ok, so maybe that last one isn't real
 
 struct Parser<'a> {
     record: &'a Book,
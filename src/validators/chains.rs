@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use super::Context;
+use crate::handlers::Handler;
+use tes3::esp::{Dialogue, DialogueInfo};
+
+pub struct ChainValidator<'a> {
+    topic: String,
+    infos: Vec<&'a DialogueInfo>,
+    topics: HashMap<String, String>,
+}
+
+impl<'a> Handler<'a> for ChainValidator<'a> {
+    fn on_info(&mut self, _: &Context, record: &'a DialogueInfo, topic: &Dialogue) {
+        if topic.id != self.topic {
+            self.check_topic();
+            topic.id.clone_into(&mut self.topic);
+        }
+        if let Some(other) = self.topics.get(&record.id) {
+            if !other.eq_ignore_ascii_case(&topic.id) {
+                println!(
+                    "Info {} in topic {} shares its ID with an info in topic {}",
+                    record.id, topic.id, other
+                );
+            }
+        } else {
+            self.topics.insert(record.id.clone(), topic.id.clone());
+        }
+        self.infos.push(record);
+    }
+
+    fn on_end(&mut self, _: &Context) {
+        self.check_topic();
+    }
+}
+
+impl ChainValidator<'_> {
+    pub fn new<'a>() -> ChainValidator<'a> {
+        ChainValidator {
+            topic: String::new(),
+            infos: Vec::new(),
+            topics: HashMap::new(),
+        }
+    }
+
+    fn check_topic(&mut self) {
+        let infos: HashMap<&str, &DialogueInfo> = self
+            .infos
+            .iter()
+            .map(|info| (info.id.as_str(), *info))
+            .collect();
+        for info in &self.infos {
+            if let Some(next) = infos.get(info.next_id.as_str()) {
+                if next.prev_id != info.id {
+                    println!(
+                        "Info {} in topic {} links to next info {} which does not link back",
+                        info.id, self.topic, next.id
+                    );
+                }
+            }
+            if let Some(prev) = infos.get(info.prev_id.as_str()) {
+                if prev.next_id != info.id {
+                    println!(
+                        "Info {} in topic {} links to previous info {} which does not link back",
+                        info.id, self.topic, prev.id
+                    );
+                }
+            }
+        }
+        // Follow the next links from every info, stopping at infos that were already checked
+        let mut checked = HashMap::new();
+        for (start, info) in self.infos.iter().enumerate() {
+            let mut current = *info;
+            while !checked.contains_key(current.id.as_str()) {
+                checked.insert(current.id.as_str(), start);
+                match infos.get(current.next_id.as_str()) {
+                    Some(next) => current = next,
+                    None => break,
+                }
+            }
+            if checked.get(current.id.as_str()) == Some(&start)
+                && infos.contains_key(current.next_id.as_str())
+            {
+                println!(
+                    "Topic {} contains a cycle through info {}",
+                    self.topic, current.id
+                );
+            }
+        }
+        self.infos.clear();
+    }
+}
@@ -0,0 +1,139 @@
+use std::collections::{HashMap, HashSet};
+
+use super::Context;
+use crate::{handlers::Handler, mwscript::Statement};
+use tes3::esp::{
+    Dialogue, DialogueInfo, FilterComparison, FilterFunction, FilterType, FilterValue, TES3Object,
+};
+
+pub struct ChoiceValidator {
+    topic: String,
+    infos: HashSet<String>,
+    links: Vec<String>,
+    offered: HashMap<i32, String>,
+    answered: Vec<(String, i32)>,
+}
+
+/// Returns the choices an info's filters respond to
+pub fn get_answered(record: &DialogueInfo) -> impl Iterator<Item = i32> + '_ {
+    record
+        .filters
+        .iter()
+        .filter(|filter| {
+            filter.filter_type == FilterType::Function
+                && filter.function == FilterFunction::Choice
+                && filter.comparison == FilterComparison::Equal
+        })
+        .map(|filter| match filter.value {
+            FilterValue::Integer(i) => i,
+            FilterValue::Float(f) => f as i32,
+        })
+}
+
+/// Returns the choices offered by the Choice calls in a script
+pub fn get_offered(statements: &[Statement]) -> Vec<i32> {
+    let mut offered = Vec::new();
+    for statement in statements {
+        statement.for_each_call(&mut |call| {
+            if !call.is("choice") {
+                return;
+            }
+            // Choice takes pairs of text and number
+            for argument in call.arguments.iter().skip(1).step_by(2) {
+                if let Some(value) = argument.as_integer() {
+                    offered.push(value);
+                }
+            }
+        });
+    }
+    offered
+}
+
+impl Handler<'_> for ChoiceValidator {
+    fn on_info(&mut self, _: &Context, record: &DialogueInfo, topic: &Dialogue) {
+        if topic.id != self.topic {
+            self.check_topic();
+            topic.id.clone_into(&mut self.topic);
+        }
+        self.infos.insert(record.id.clone());
+        self.links.push(record.prev_id.clone());
+        self.links.push(record.next_id.clone());
+        for value in get_answered(record) {
+            self.answered.push((record.id.clone(), value));
+        }
+    }
+
+    fn on_script_ast(
+        &mut self,
+        _: &Context,
+        record: &TES3Object,
+        statements: &[Statement],
+        _: &Dialogue,
+    ) {
+        let TES3Object::DialogueInfo(info) = record else {
+            return;
+        };
+        for value in get_offered(statements) {
+            self.offered.entry(value).or_insert_with(|| info.id.clone());
+        }
+    }
+
+    fn on_end(&mut self, _: &Context) {
+        self.check_topic();
+    }
+}
+
+impl ChoiceValidator {
+    pub fn new() -> Self {
+        Self {
+            topic: String::new(),
+            infos: HashSet::new(),
+            links: Vec::new(),
+            offered: HashMap::new(),
+            answered: Vec::new(),
+        }
+    }
+
+    /// Returns true if the topic's infos only link to each other, meaning it is not a topic from a master
+    fn is_defined(&self) -> bool {
+        self.links
+            .iter()
+            .all(|link| link.is_empty() || self.infos.contains(link))
+    }
+
+    fn check_topic(&mut self) {
+        // Responses in master topics are checked by the extended validator
+        if self.is_defined() {
+            self.report();
+        }
+        self.infos.clear();
+        self.links.clear();
+        self.offered.clear();
+        self.answered.clear();
+    }
+
+    fn report(&self) {
+        let mut values = HashSet::new();
+        for (id, value) in &self.answered {
+            values.insert(*value);
+            if !self.offered.contains_key(value) {
+                println!(
+                    "Info {} in topic {} checks for choice {} which is never offered",
+                    id, self.topic, value
+                );
+            }
+        }
+        let mut unanswered: Vec<_> = self
+            .offered
+            .iter()
+            .filter(|(value, _)| !values.contains(value))
+            .collect();
+        unanswered.sort();
+        for (value, id) in unanswered {
+            println!(
+                "Info {} in topic {} offers choice {} which no info answers",
+                id, self.topic, value
+            );
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::{HashMap, HashSet};
+
+use super::Context;
+use crate::{
+    context::Engine,
+    handlers::Handler,
+    mwscript::{get_extensions, get_functions, Call, Signature, Statement, StatementKind},
+};
+use tes3::esp::{Dialogue, TES3Object};
+
+pub struct CompileValidator {
+    engine: Engine,
+    functions: HashMap<&'static str, Signature>,
+    unavailable: Vec<(Engine, HashMap<&'static str, Signature>)>,
+}
+
+#[derive(PartialEq)]
+enum Block {
+    If,
+    While,
+}
+
+impl Handler<'_> for CompileValidator {
+    fn on_script_ast(
+        &mut self,
+        _: &Context,
+        record: &TES3Object,
+        statements: &[Statement],
+        topic: &Dialogue,
+    ) {
+        let (source, is_script) = match record {
+            TES3Object::Script(script) => (format!("Script {}", script.id), true),
+            TES3Object::DialogueInfo(info) => {
+                (format!("Info {} in topic {}", info.id, topic.id), false)
+            }
+            _ => return,
+        };
+        let begins = matches!(
+            statements.first().map(|s| &s.kind),
+            Some(StatementKind::Begin(_))
+        );
+        if is_script && !begins {
+            println!("{} does not start with Begin", source);
+        }
+        let mut blocks = Vec::new();
+        let mut locals = HashSet::new();
+        let mut ended = false;
+        for statement in statements {
+            let line = statement.line;
+            match &statement.kind {
+                StatementKind::End => ended = true,
+                StatementKind::Declaration(_, name)
+                    if !locals.insert(name.to_ascii_lowercase()) =>
+                {
+                    println!(
+                        "{} declares local {} multiple times on line {}",
+                        source, name, line
+                    );
+                }
+                StatementKind::If(_) => blocks.push((Block::If, line)),
+                StatementKind::While(_) => blocks.push((Block::While, line)),
+                StatementKind::ElseIf(_) | StatementKind::Else
+                    if !matches!(blocks.last(), Some((Block::If, _))) =>
+                {
+                    println!("{} has else without if on line {}", source, line);
+                }
+                StatementKind::EndIf => {
+                    if matches!(blocks.last(), Some((Block::If, _))) {
+                        blocks.pop();
+                    } else {
+                        println!("{} has endif without if on line {}", source, line);
+                    }
+                }
+                StatementKind::EndWhile => {
+                    if matches!(blocks.last(), Some((Block::While, _))) {
+                        blocks.pop();
+                    } else {
+                        println!("{} has endwhile without while on line {}", source, line);
+                    }
+                }
+                StatementKind::Invalid(reason) => {
+                    println!("{} fails to compile on line {}: {}", source, line, reason);
+                }
+                _ => {}
+            }
+            statement.for_each_call(&mut |call| self.check_call(&source, call, line));
+        }
+        for (block, line) in blocks {
+            let keyword = if block == Block::If { "if" } else { "while" };
+            println!("{} has unclosed {} on line {}", source, keyword, line);
+        }
+        if is_script && !ended {
+            println!("{} is missing End", source);
+        }
+    }
+}
+
+impl CompileValidator {
+    pub fn new(context: &Context) -> Self {
+        let functions = get_functions(context.engine);
+        let unavailable = Engine::ALL
+            .into_iter()
+            .filter(|engine| *engine != context.engine)
+            .map(|engine| {
+                let mut extensions = get_extensions(engine);
+                extensions.retain(|name, _| !functions.contains_key(name));
+                (engine, extensions)
+            })
+            .collect();
+        Self {
+            engine: context.engine,
+            functions,
+            unavailable,
+        }
+    }
+
+    fn check_call(&self, source: &str, call: &Call, line: usize) {
+        let name = call.function.to_ascii_lowercase();
+        let Some(&(min, max)) = self.functions.get(name.as_str()) else {
+            let engines: Vec<_> = self
+                .unavailable
+                .iter()
+                .filter(|(_, functions)| functions.contains_key(name.as_str()))
+                .map(|(engine, _)| engine.name())
+                .collect();
+            if engines.is_empty() {
+                println!(
+                    "{} calls unknown function {} on line {}",
+                    source, call.function, line
+                );
+            } else {
+                println!(
+                    "{} calls {} function {} on line {} which is not available in {}",
+                    source,
+                    engines.join(" or "),
+                    call.function,
+                    line,
+                    self.engine.name()
+                );
+            }
+            return;
+        };
+        let count = call.arguments.len();
+        if count >= min && max.is_none_or(|max| count <= max) {
+            return;
+        }
+        let expected = match max {
+            Some(max) if max == min => min.to_string(),
+            Some(max) => format!("{} to {}", min, max),
+            None => format!("at least {}", min),
+        };
+        println!(
+            "{} calls {} with {} arguments on line {}, expected {}",
+            source, call.function, count, line, expected
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use super::Context;
-use crate::{context::Mode, handlers::Handler};
+use crate::{context::Mode, encoding::Encoding, handlers::Handler};
 use regex::{Error, Regex, RegexBuilder};
 use tes3::esp::{
     Dialogue, DialogueInfo, DialogueType, FilterComparison, FilterFunction, FilterType,
@@ -14,7 +14,7 @@ pub struct DialogueValidator {
     short_ellipsis: Regex,
     punctuation_whitespace: Regex,
     punctuation_double: Regex,
-    article_pc: Regex,
+    article_pc: Option<Regex>,
     overrides: Regex,
 }
 
@@ -86,7 +86,7 @@ impl Handler<'_> for DialogueValidator {
                     record.id, topic.id
                 );
             }
-            if self.article_pc.is_match(text) {
+            if self.article_pc.as_ref().is_some_and(|r| r.is_match(text)) {
                 println!(
                     "Info {} in topic {} contains an indefinite article followed by a PC variable",
                     record.id, topic.id
@@ -330,17 +330,38 @@ impl Handler<'_> for DialogueValidator {
 }
 
 impl DialogueValidator {
-    pub fn new() -> Result<Self, Error> {
+    pub fn new(context: &Context) -> Result<Self, Error> {
         let blank = RegexBuilder::new(r"(^|\n)\s*;\s*SV:\s*intentionally\s+left\s+blank\s*($|\n)")
             .case_insensitive(true)
             .build()?;
         let double_spaces = Regex::new(r"[^\S\r\n]{2,}")?;
-        let short_ellipsis = Regex::new(r"[^.]\.{2}[^.?]")?;
-        let punctuation_whitespace = Regex::new(r"\s[.,:;?]($|\s)")?;
-        let punctuation_double = Regex::new(r"[,:;]{2,}")?;
-        let article_pc = RegexBuilder::new(r"(^|\s)an?\s+%PC")
-            .case_insensitive(true)
-            .build()?;
+        let short_ellipsis = if context.encoding == Encoding::Win1251 {
+            // Russian punctuation allows ?.. and !..
+            Regex::new(r"[^.?!]\.{2}[^.?]")?
+        } else {
+            Regex::new(r"[^.]\.{2}[^.?]")?
+        };
+        let punctuation_whitespace = if context.french {
+            // French puts a space before colons, semicolons, and question marks
+            Regex::new(r"\s[.,]($|\s)")?
+        } else {
+            Regex::new(r"\s[.,:;?]($|\s)")?
+        };
+        let punctuation_double = if context.encoding == Encoding::English {
+            Regex::new(r"[,:;]{2,}")?
+        } else {
+            Regex::new(r"[,:;]{2,}|««|»»|„„|““|””")?
+        };
+        // Indefinite articles only need checking in English
+        let article_pc = if context.encoding == Encoding::English {
+            Some(
+                RegexBuilder::new(r"(^|\s)an?\s+%PC")
+                    .case_insensitive(true)
+                    .build()?,
+            )
+        } else {
+            None
+        };
         let overrides = RegexBuilder::new(r"(^|\n)\s*;\s*SV:\s*vanilla\s+override\s*($|\n)")
             .case_insensitive(true)
             .build()?;
@@ -7,6 +7,23 @@ pub struct DuplicateRefValidator {
     threshold: f32,
 }
 
+pub fn is_duplicate(a: &Reference, b: &Reference, threshold: f32) -> bool {
+    a.id.eq_ignore_ascii_case(&b.id)
+        && a.rotation == b.rotation
+        && a.scale.unwrap_or(1.) == b.scale.unwrap_or(1.)
+        && translation(a.translation, b.translation, threshold)
+}
+
+fn translation(a: [f32; 3], b: [f32; 3], threshold: f32) -> bool {
+    if threshold == 0. {
+        return a == b;
+    }
+    let [x1, y1, z1] = a;
+    let [x2, y2, z2] = b;
+    let d2 = (x1 - x2) * (x1 - x2) + (y1 - y2) * (y1 - y2) + (z1 - z2) * (z1 - z2);
+    d2.abs() <= threshold
+}
+
 impl Handler<'_> for DuplicateRefValidator {
     fn on_cellref(
         &mut self,
@@ -24,11 +41,7 @@ impl Handler<'_> for DuplicateRefValidator {
             if other.deleted == Some(true) {
                 continue;
             }
-            if other.id.eq_ignore_ascii_case(&reference.id)
-                && reference.rotation == other.rotation
-                && reference.scale.unwrap_or(1.) == other.scale.unwrap_or(1.)
-                && self.translation(reference.translation, other.translation)
-            {
+            if is_duplicate(reference, other, self.threshold) {
                 println!(
                     "Cell {} contains duplicate reference {} at position {:?} {:?}",
                     record.editor_id(),
@@ -41,22 +54,16 @@ impl Handler<'_> for DuplicateRefValidator {
     }
 }
 
+pub fn get_threshold(args: &ArgMatches) -> f32 {
+    args.get_one::<f32>("duplicatethreshold")
+        .unwrap_or(&0.)
+        .max(0.)
+}
+
 impl DuplicateRefValidator {
     pub fn new(args: &ArgMatches) -> Self {
-        let threshold = args
-            .get_one::<f32>("duplicatethreshold")
-            .unwrap_or(&0.)
-            .max(0.);
-        DuplicateRefValidator { threshold }
-    }
-
-    fn translation(&self, a: [f32; 3], b: [f32; 3]) -> bool {
-        if self.threshold == 0. {
-            return a == b;
+        DuplicateRefValidator {
+            threshold: get_threshold(args),
         }
-        let [x1, y1, z1] = a;
-        let [x2, y2, z2] = b;
-        let d2 = (x1 - x2) * (x1 - x2) + (y1 - y2) * (y1 - y2) + (z1 - z2) * (z1 - z2);
-        d2.abs() <= self.threshold
     }
 }
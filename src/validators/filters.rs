@@ -0,0 +1,290 @@
+use std::collections::{HashMap, HashSet};
+
+use super::Context;
+use crate::{
+    handlers::Handler,
+    mwscript::{parse, StatementKind},
+    util::{ci_starts_with, has_faction},
+    validators::journals::can_pass,
+};
+use tes3::esp::{
+    Dialogue, DialogueInfo, DialogueType2, EditorId, FilterComparison, FilterType, FilterValue,
+    QuestState, TES3Object,
+};
+
+#[derive(Clone, Copy, PartialEq)]
+enum TargetKind {
+    Journal,
+    Item,
+    Global,
+    Actor,
+    Class,
+    Faction,
+    Race,
+    Cell,
+}
+
+struct Target {
+    source: String,
+    kind: TargetKind,
+    id: String,
+    index: Option<(FilterComparison, i32)>,
+}
+
+struct LocalTest {
+    source: String,
+    speaker: String,
+    variable: String,
+}
+
+/// Collects the records dialogue filters can refer to and the filters themselves so they can be resolved at the end
+pub struct FilterTargets {
+    records: HashMap<String, TargetKind>,
+    actor_scripts: HashMap<String, String>,
+    scripts: HashMap<String, HashSet<String>>,
+    journals: HashMap<String, HashSet<i32>>,
+    cells: HashSet<String>,
+    targets: Vec<Target>,
+    locals: Vec<LocalTest>,
+}
+
+pub struct FilterValidator {
+    targets: FilterTargets,
+}
+
+fn get_kind(filter_type: FilterType) -> Option<TargetKind> {
+    match filter_type {
+        FilterType::Journal => Some(TargetKind::Journal),
+        FilterType::Item => Some(TargetKind::Item),
+        FilterType::Global => Some(TargetKind::Global),
+        FilterType::Dead | FilterType::NotId => Some(TargetKind::Actor),
+        FilterType::NotClass => Some(TargetKind::Class),
+        FilterType::NotFaction => Some(TargetKind::Faction),
+        FilterType::NotRace => Some(TargetKind::Race),
+        FilterType::NotCell => Some(TargetKind::Cell),
+        _ => None,
+    }
+}
+
+impl FilterTargets {
+    pub fn new() -> Self {
+        Self {
+            records: HashMap::new(),
+            actor_scripts: HashMap::new(),
+            scripts: HashMap::new(),
+            journals: HashMap::new(),
+            cells: HashSet::new(),
+            targets: Vec::new(),
+            locals: Vec::new(),
+        }
+    }
+
+    pub fn add_record(&mut self, record: &TES3Object) {
+        let kind = match record {
+            TES3Object::Alchemy(_)
+            | TES3Object::Apparatus(_)
+            | TES3Object::Armor(_)
+            | TES3Object::Book(_)
+            | TES3Object::Clothing(_)
+            | TES3Object::Ingredient(_)
+            | TES3Object::Light(_)
+            | TES3Object::Lockpick(_)
+            | TES3Object::MiscItem(_)
+            | TES3Object::Probe(_)
+            | TES3Object::RepairItem(_)
+            | TES3Object::Weapon(_) => TargetKind::Item,
+            TES3Object::Npc(npc) => {
+                self.add_actor(record, &npc.script);
+                TargetKind::Actor
+            }
+            TES3Object::Creature(creature) => {
+                self.add_actor(record, &creature.script);
+                TargetKind::Actor
+            }
+            TES3Object::GlobalVariable(_) => TargetKind::Global,
+            TES3Object::Class(_) => TargetKind::Class,
+            TES3Object::Faction(_) => TargetKind::Faction,
+            TES3Object::Race(_) => TargetKind::Race,
+            TES3Object::Dialogue(dialogue) if dialogue.dialogue_type == DialogueType2::Journal => {
+                TargetKind::Journal
+            }
+            TES3Object::Script(script) => {
+                let locals = parse(&script.text)
+                    .into_iter()
+                    .filter_map(|statement| match statement.kind {
+                        StatementKind::Declaration(_, name) => Some(name.to_ascii_lowercase()),
+                        _ => None,
+                    })
+                    .collect();
+                self.scripts.insert(script.id.to_ascii_lowercase(), locals);
+                return;
+            }
+            TES3Object::Cell(cell) => {
+                if !cell.name.is_empty() {
+                    self.cells.insert(cell.name.to_ascii_lowercase());
+                }
+                return;
+            }
+            TES3Object::Region(region) => {
+                self.cells.insert(region.name.to_ascii_lowercase());
+                return;
+            }
+            _ => return,
+        };
+        self.records
+            .insert(record.editor_id_ascii_lowercase().into_owned(), kind);
+    }
+
+    fn add_actor(&mut self, record: &TES3Object, script: &str) {
+        let id = record.editor_id_ascii_lowercase().into_owned();
+        if script.is_empty() {
+            self.actor_scripts.remove(&id);
+        } else {
+            self.actor_scripts.insert(id, script.to_ascii_lowercase());
+        }
+    }
+
+    /// Adds an info. If check is set, its filters and speaker fields are resolved at the end.
+    pub fn add_info(&mut self, record: &DialogueInfo, topic: &Dialogue, check: bool) {
+        if topic.dialogue_type == DialogueType2::Journal {
+            if record.quest_state != Some(QuestState::Name) {
+                self.journals
+                    .entry(topic.id.to_ascii_lowercase())
+                    .or_default()
+                    .insert(record.data.disposition);
+            }
+            return;
+        }
+        if !check {
+            return;
+        }
+        let source = format!("Info {} in topic {}", record.id, topic.id);
+        let fields = [
+            (TargetKind::Race, &record.speaker_race),
+            (TargetKind::Class, &record.speaker_class),
+            (TargetKind::Faction, &record.speaker_faction),
+            (TargetKind::Faction, &record.player_faction),
+        ];
+        for (kind, id) in fields {
+            let set = if kind == TargetKind::Faction {
+                has_faction(id)
+            } else {
+                !id.is_empty()
+            };
+            if set {
+                self.targets.push(Target {
+                    source: source.clone(),
+                    kind,
+                    id: id.clone(),
+                    index: None,
+                });
+            }
+        }
+        for filter in &record.filters {
+            let is_local = matches!(filter.filter_type, FilterType::Local | FilterType::NotLocal);
+            if is_local && !record.speaker_id.is_empty() {
+                self.locals.push(LocalTest {
+                    source: source.clone(),
+                    speaker: record.speaker_id.to_ascii_lowercase(),
+                    variable: filter.id.clone(),
+                });
+            }
+            let Some(kind) = get_kind(filter.filter_type) else {
+                continue;
+            };
+            let value = match filter.value {
+                FilterValue::Integer(i) => i,
+                FilterValue::Float(f) => f as i32,
+            };
+            self.targets.push(Target {
+                source: source.clone(),
+                kind,
+                id: filter.id.clone(),
+                index: Some((filter.comparison, value)),
+            });
+        }
+    }
+
+    /// Reports all unresolved filters. If incomplete is set, only the locals of speakers whose script was seen are checked.
+    pub fn report(&self, incomplete: bool) {
+        for test in &self.locals {
+            let Some(script) = self.actor_scripts.get(&test.speaker) else {
+                if self.records.get(&test.speaker) == Some(&TargetKind::Actor) {
+                    println!(
+                        "{} filters on local {} but {} does not have a script",
+                        test.source, test.variable, test.speaker
+                    );
+                }
+                continue;
+            };
+            if let Some(locals) = self.scripts.get(script) {
+                if !locals.contains(&test.variable.to_ascii_lowercase()) {
+                    println!(
+                        "{} filters on local {} which {} does not declare",
+                        test.source, test.variable, script
+                    );
+                }
+            }
+        }
+        if incomplete {
+            return;
+        }
+        for target in &self.targets {
+            let id = target.id.to_ascii_lowercase();
+            let defined = if target.kind == TargetKind::Cell {
+                self.cells.iter().any(|cell| ci_starts_with(cell, &id))
+            } else {
+                self.records.get(&id) == Some(&target.kind)
+            };
+            if !defined {
+                let kind = match target.kind {
+                    TargetKind::Journal => "journal",
+                    TargetKind::Item => "item",
+                    TargetKind::Global => "global",
+                    TargetKind::Actor => "actor",
+                    TargetKind::Class => "class",
+                    TargetKind::Faction => "faction",
+                    TargetKind::Race => "race",
+                    TargetKind::Cell => "cell",
+                };
+                println!(
+                    "{} filters on undefined {} {}",
+                    target.source, kind, target.id
+                );
+            } else if let (TargetKind::Journal, Some((comparison, value))) =
+                (target.kind, target.index)
+            {
+                let indices = self.journals.get(&id).into_iter().flatten();
+                if !can_pass(comparison, value, indices) {
+                    println!(
+                        "{} tests journal {} for index {} which does not exist",
+                        target.source, target.id, value
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl Handler<'_> for FilterValidator {
+    fn on_record(&mut self, _: &Context, record: &TES3Object) {
+        self.targets.add_record(record);
+    }
+
+    fn on_info(&mut self, _: &Context, record: &DialogueInfo, topic: &Dialogue) {
+        self.targets.add_info(record, topic, true);
+    }
+
+    fn on_end(&mut self, _: &Context) {
+        // Without masters, only locals of speakers defined in this file can be resolved
+        self.targets.report(true);
+    }
+}
+
+impl FilterValidator {
+    pub fn new() -> Self {
+        Self {
+            targets: FilterTargets::new(),
+        }
+    }
+}
@@ -0,0 +1,359 @@
+use std::collections::{HashMap, HashSet};
+
+use super::Context;
+use crate::{
+    handlers::Handler,
+    mwscript::{parse, Call, Expression, Statement, StatementKind, Variable},
+    util::{ci_starts_with, get_script},
+};
+use tes3::esp::{Dialogue, DialogueType2, EditorId, TES3Object};
+
+#[derive(Clone, Copy)]
+enum UsageKind {
+    Object,
+    Script,
+    Global,
+    Variable,
+    Journal,
+    Topic,
+    Cell,
+    CellPrefix,
+}
+
+struct Usage {
+    source: String,
+    line: usize,
+    id: String,
+    kind: UsageKind,
+    variable: Option<String>,
+}
+
+/// Collects the records scripts can refer to and the references made by scripts so they can be resolved at the end
+pub struct ScriptIdentifiers {
+    objects: HashSet<String>,
+    object_scripts: HashMap<String, String>,
+    scripts: HashMap<String, HashSet<String>>,
+    globals: HashSet<String>,
+    journals: HashSet<String>,
+    topics: HashSet<String>,
+    cells: HashSet<String>,
+    usages: Vec<Usage>,
+}
+
+pub struct IdentifierValidator {
+    identifiers: ScriptIdentifiers,
+}
+
+fn get_locals(statements: &[Statement]) -> HashSet<String> {
+    statements
+        .iter()
+        .filter_map(|statement| match &statement.kind {
+            StatementKind::Declaration(_, name) => Some(name.to_ascii_lowercase()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn get_argument_kinds(function: &str) -> &'static [(usize, UsageKind)] {
+    match function {
+        "additem" | "removeitem" | "drop" | "equip" | "getitemcount" | "hasitemequipped"
+        | "placeatme" | "placeatpc" | "placeitem" | "removesoulgem" | "hassoulgem"
+        | "dropsoulgem" | "getdeadcount" | "getdistance" | "getdetected" | "getlos"
+        | "getlineofsight" | "gettarget" | "startcombat" | "aiactivate" | "aiescort"
+        | "aifollow" => &[(0, UsageKind::Object)],
+        "addsoulgem"
+        | "addtolevcreature"
+        | "addtolevitem"
+        | "removefromlevcreature"
+        | "removefromlevitem" => &[(0, UsageKind::Object), (1, UsageKind::Object)],
+        "placeitemcell" => &[(0, UsageKind::Object), (1, UsageKind::Cell)],
+        "aiescortcell" | "aifollowcell" => &[(0, UsageKind::Object), (1, UsageKind::Cell)],
+        "journal" | "setjournalindex" | "getjournalindex" => &[(0, UsageKind::Journal)],
+        "addtopic" => &[(0, UsageKind::Topic)],
+        "startscript" | "stopscript" | "scriptrunning" => &[(0, UsageKind::Script)],
+        "positioncell" => &[(4, UsageKind::Cell)],
+        "centeroncell" | "coc" => &[(0, UsageKind::Cell)],
+        "getpccell" => &[(0, UsageKind::CellPrefix)],
+        _ => &[],
+    }
+}
+
+impl ScriptIdentifiers {
+    pub fn new() -> Self {
+        let mut objects = HashSet::new();
+        objects.insert("player".to_string());
+        Self {
+            objects,
+            object_scripts: HashMap::new(),
+            scripts: HashMap::new(),
+            globals: HashSet::new(),
+            journals: HashSet::new(),
+            topics: HashSet::new(),
+            cells: HashSet::new(),
+            usages: Vec::new(),
+        }
+    }
+
+    pub fn add_record(&mut self, record: &TES3Object) {
+        match record {
+            TES3Object::Script(script) => {
+                self.scripts.insert(
+                    script.id.to_ascii_lowercase(),
+                    get_locals(&parse(&script.text)),
+                );
+            }
+            TES3Object::GlobalVariable(_) => {
+                self.globals
+                    .insert(record.editor_id_ascii_lowercase().into_owned());
+            }
+            TES3Object::Dialogue(dialogue) => {
+                if dialogue.dialogue_type == DialogueType2::Journal {
+                    self.journals.insert(dialogue.id.to_ascii_lowercase());
+                } else if dialogue.dialogue_type == DialogueType2::Topic {
+                    self.topics.insert(dialogue.id.to_ascii_lowercase());
+                }
+            }
+            TES3Object::Cell(cell) => {
+                if !cell.name.is_empty() {
+                    self.cells.insert(cell.name.to_ascii_lowercase());
+                }
+            }
+            TES3Object::Region(region) => {
+                self.cells.insert(region.name.to_ascii_lowercase());
+            }
+            TES3Object::LeveledCreature(_) | TES3Object::LeveledItem(_) | TES3Object::Static(_) => {
+                self.objects
+                    .insert(record.editor_id_ascii_lowercase().into_owned());
+            }
+            _ => {
+                if let Some(script) = get_script(record) {
+                    let id = record.editor_id_ascii_lowercase().into_owned();
+                    if !script.is_empty() {
+                        self.object_scripts
+                            .insert(id.clone(), script.to_ascii_lowercase());
+                    }
+                    self.objects.insert(id);
+                }
+            }
+        }
+    }
+
+    pub fn add_script(&mut self, source: &str, is_script: bool, statements: &[Statement]) {
+        let locals = if is_script {
+            Some(get_locals(statements))
+        } else {
+            None
+        };
+        let mut usages = Vec::new();
+        let mut add = |line: usize, id: &str, kind: UsageKind, variable: Option<&String>| {
+            usages.push(Usage {
+                source: source.to_string(),
+                line,
+                id: id.to_ascii_lowercase(),
+                kind,
+                variable: variable.cloned(),
+            })
+        };
+        for statement in statements {
+            let line = statement.line;
+            if let StatementKind::Set(
+                Variable {
+                    reference: Some(id),
+                    name,
+                },
+                _,
+            ) = &statement.kind
+            {
+                add(line, id, UsageKind::Object, Some(name));
+            } else if let (
+                StatementKind::Set(
+                    Variable {
+                        reference: None,
+                        name,
+                    },
+                    _,
+                ),
+                Some(locals),
+            ) = (&statement.kind, &locals)
+            {
+                if !locals.contains(&name.to_ascii_lowercase()) {
+                    add(line, name, UsageKind::Variable, None);
+                }
+            }
+            match &statement.kind {
+                StatementKind::Set(_, value)
+                | StatementKind::If(value)
+                | StatementKind::ElseIf(value)
+                | StatementKind::While(value) => {
+                    self.add_expression(value, line, locals.as_ref(), &mut add)
+                }
+                _ => {}
+            }
+            statement.for_each_call(&mut |call| Self::add_call(call, line, &mut add));
+        }
+        self.usages.append(&mut usages);
+    }
+
+    fn add_expression(
+        &self,
+        expression: &Expression,
+        line: usize,
+        locals: Option<&HashSet<String>>,
+        add: &mut impl FnMut(usize, &str, UsageKind, Option<&String>),
+    ) {
+        match expression {
+            Expression::Variable(Variable {
+                reference: Some(id),
+                name,
+            }) => add(line, id, UsageKind::Object, Some(name)),
+            Expression::Variable(Variable {
+                reference: None,
+                name,
+            }) => {
+                // Locals look the same as globals
+                if let Some(locals) = locals {
+                    if !locals.contains(&name.to_ascii_lowercase()) {
+                        add(line, name, UsageKind::Global, None);
+                    }
+                }
+            }
+            Expression::Negate(value) => self.add_expression(value, line, locals, add),
+            Expression::Binary(left, _, right) => {
+                self.add_expression(left, line, locals, add);
+                self.add_expression(right, line, locals, add);
+            }
+            _ => {}
+        }
+    }
+
+    fn add_call(
+        call: &Call,
+        line: usize,
+        add: &mut impl FnMut(usize, &str, UsageKind, Option<&String>),
+    ) {
+        if let Some(reference) = &call.reference {
+            add(line, reference, UsageKind::Object, None);
+        }
+        for (index, kind) in get_argument_kinds(&call.function.to_ascii_lowercase()) {
+            if let Some(id) = call.get_id(*index) {
+                add(line, id, *kind, None);
+            }
+        }
+    }
+
+    /// Reports all unresolved identifiers. If incomplete is set, only identifiers that resolve to records that were seen are checked.
+    pub fn report(&self, incomplete: bool) {
+        for usage in &self.usages {
+            let id = &usage.id;
+            if let Some(variable) = &usage.variable {
+                self.check_member(usage, variable, incomplete);
+                continue;
+            }
+            let defined = match usage.kind {
+                UsageKind::Object => self.objects.contains(id),
+                UsageKind::Script => self.scripts.contains_key(id),
+                UsageKind::Global | UsageKind::Variable => self.globals.contains(id),
+                UsageKind::Journal => self.journals.contains(id),
+                UsageKind::Topic => self.topics.contains(id),
+                UsageKind::Cell => self.cells.contains(id),
+                UsageKind::CellPrefix => self.cells.iter().any(|cell| ci_starts_with(cell, id)),
+            };
+            if defined || incomplete {
+                continue;
+            }
+            if let UsageKind::Variable = usage.kind {
+                println!(
+                    "{} sets undeclared variable {} on line {}",
+                    usage.source, id, usage.line
+                );
+                continue;
+            }
+            let kind = match usage.kind {
+                UsageKind::Object => "undefined object",
+                UsageKind::Script => "undefined script",
+                UsageKind::Global => "undefined global",
+                UsageKind::Journal => "undefined journal",
+                UsageKind::Topic => "undefined topic",
+                _ => "unknown cell",
+            };
+            println!(
+                "{} uses {} {} on line {}",
+                usage.source, kind, id, usage.line
+            );
+        }
+    }
+
+    fn check_member(&self, usage: &Usage, variable: &str, incomplete: bool) {
+        let id = &usage.id;
+        let script = if self.scripts.contains_key(id) {
+            id
+        } else if let Some(script) = self.object_scripts.get(id) {
+            script
+        } else {
+            if self.objects.contains(id) {
+                println!(
+                    "{} accesses {}.{} on line {} but {} does not have a script",
+                    usage.source, id, variable, usage.line, id
+                );
+            } else if !incomplete {
+                println!(
+                    "{} uses undefined object {} on line {}",
+                    usage.source, id, usage.line
+                );
+            }
+            return;
+        };
+        if let Some(locals) = self.scripts.get(script) {
+            if !locals.contains(&variable.to_ascii_lowercase()) {
+                println!(
+                    "{} accesses {}.{} on line {} but {} does not declare {}",
+                    usage.source, id, variable, usage.line, script, variable
+                );
+            }
+        } else if !incomplete {
+            println!(
+                "{} uses undefined script {} on line {}",
+                usage.source, script, usage.line
+            );
+        }
+    }
+}
+
+impl Handler<'_> for IdentifierValidator {
+    fn on_record(&mut self, _: &Context, record: &TES3Object) {
+        self.identifiers.add_record(record);
+    }
+
+    fn on_script_ast(
+        &mut self,
+        _: &Context,
+        record: &TES3Object,
+        statements: &[Statement],
+        topic: &Dialogue,
+    ) {
+        match record {
+            TES3Object::Script(script) => {
+                let source = format!("Script {}", script.id);
+                self.identifiers.add_script(&source, true, statements);
+            }
+            TES3Object::DialogueInfo(info) => {
+                let source = format!("Info {} in topic {}", info.id, topic.id);
+                self.identifiers.add_script(&source, false, statements);
+            }
+            _ => {}
+        }
+    }
+
+    fn on_end(&mut self, _: &Context) {
+        // Without masters, only references to records in this file can be resolved
+        self.identifiers.report(true);
+    }
+}
+
+impl IdentifierValidator {
+    pub fn new() -> Self {
+        Self {
+            identifiers: ScriptIdentifiers::new(),
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use std::collections::{HashMap, HashSet};
+
+use super::Context;
+use crate::{
+    handlers::Handler,
+    mwscript::{Expression, Statement},
+};
+use tes3::esp::{
+    Dialogue, DialogueInfo, DialogueType2, FilterComparison, FilterType, FilterValue, ObjectFlags,
+    QuestState, TES3Object,
+};
+
+struct Test {
+    source: String,
+    journal: String,
+    comparison: FilterComparison,
+    value: i32,
+}
+
+pub struct JournalValidator {
+    topic: String,
+    entries: Vec<(i32, Option<QuestState>)>,
+    journals: HashMap<String, String>,
+    set_indices: HashMap<String, HashSet<i32>>,
+    tests: Vec<Test>,
+}
+
+/// Returns true if a journal filter can pass given the indices the journal can be set to
+pub fn can_pass<'a>(
+    comparison: FilterComparison,
+    value: i32,
+    mut indices: impl Iterator<Item = &'a i32>,
+) -> bool {
+    match comparison {
+        // Every journal starts out at index 0
+        FilterComparison::Equal if value == 0 => true,
+        FilterComparison::GreaterEqual if value <= 0 => true,
+        FilterComparison::Equal => indices.any(|index| *index == value),
+        FilterComparison::Greater => indices.any(|index| *index > value),
+        FilterComparison::GreaterEqual => indices.any(|index| *index >= value),
+        FilterComparison::Less | FilterComparison::LessEqual | FilterComparison::NotEqual => true,
+    }
+}
+
+impl Handler<'_> for JournalValidator {
+    fn on_info(&mut self, _: &Context, record: &DialogueInfo, topic: &Dialogue) {
+        if topic.id != self.topic {
+            self.check_journal();
+            topic.id.clone_into(&mut self.topic);
+            if topic.dialogue_type == DialogueType2::Journal {
+                self.journals
+                    .insert(topic.id.to_ascii_lowercase(), topic.id.clone());
+            }
+        }
+        if topic.dialogue_type == DialogueType2::Journal {
+            if !record.flags.contains(ObjectFlags::DELETED) {
+                self.entries
+                    .push((record.data.disposition, record.quest_state));
+            }
+            return;
+        }
+        for filter in &record.filters {
+            if filter.filter_type == FilterType::Journal {
+                let value = match filter.value {
+                    FilterValue::Integer(i) => i,
+                    FilterValue::Float(f) => f as i32,
+                };
+                self.tests.push(Test {
+                    source: format!("Info {} in topic {}", record.id, topic.id),
+                    journal: filter.id.to_ascii_lowercase(),
+                    comparison: filter.comparison,
+                    value,
+                });
+            }
+        }
+    }
+
+    fn on_script_ast(
+        &mut self,
+        _: &Context,
+        _: &TES3Object,
+        statements: &[Statement],
+        _: &Dialogue,
+    ) {
+        for statement in statements {
+            statement.for_each_call(&mut |call| {
+                if !call.is("journal") && !call.is("setjournalindex") {
+                    return;
+                }
+                let index = call.arguments.get(1).and_then(Expression::as_integer);
+                if let (Some(id), Some(index)) = (call.get_id(0), index) {
+                    self.set_indices
+                        .entry(id.to_ascii_lowercase())
+                        .or_default()
+                        .insert(index);
+                }
+            });
+        }
+    }
+
+    fn on_end(&mut self, _: &Context) {
+        self.check_journal();
+        for test in &self.tests {
+            let Some(journal) = self.journals.get(&test.journal) else {
+                continue;
+            };
+            let indices = self.set_indices.get(&test.journal).into_iter().flatten();
+            if !can_pass(test.comparison, test.value, indices) {
+                println!(
+                    "{} tests journal {} for index {} which is never set",
+                    test.source, journal, test.value
+                );
+            }
+        }
+    }
+}
+
+impl JournalValidator {
+    pub fn new() -> Self {
+        Self {
+            topic: String::new(),
+            entries: Vec::new(),
+            journals: HashMap::new(),
+            set_indices: HashMap::new(),
+            tests: Vec::new(),
+        }
+    }
+
+    fn check_journal(&mut self) {
+        if !self.journals.contains_key(&self.topic.to_ascii_lowercase()) {
+            self.entries.clear();
+            return;
+        }
+        let id = &self.topic;
+        let names = self
+            .entries
+            .iter()
+            .filter(|(_, state)| *state == Some(QuestState::Name))
+            .count();
+        let mut entries: Vec<_> = self
+            .entries
+            .iter()
+            .filter(|(_, state)| *state != Some(QuestState::Name))
+            .collect();
+        entries.sort_by_key(|(index, _)| *index);
+        // Plugins that only add entries to a quest from a master are checked in extended mode
+        if names > 0 || entries.iter().any(|(index, _)| *index == 0) {
+            if names == 0 {
+                println!("Journal {} does not have a quest name", id);
+            } else if names > 1 {
+                println!("Journal {} has {} quest names", id, names);
+            }
+            if !entries
+                .iter()
+                .any(|(_, state)| *state == Some(QuestState::Finished))
+            {
+                println!("Journal {} is never finished", id);
+            }
+        } else {
+            // Master scripts and dialogue can set indices this validator never sees
+            self.journals.remove(&id.to_ascii_lowercase());
+        }
+        let mut finished = None;
+        for (i, (index, state)) in entries.iter().enumerate() {
+            if i > 0 && entries[i - 1].0 == *index {
+                println!("Journal {} has multiple entries for index {}", id, index);
+            }
+            match state {
+                Some(QuestState::Finished) => {
+                    finished.get_or_insert(*index);
+                }
+                Some(QuestState::Restart) => finished = None,
+                _ => {
+                    if let Some(finished) = finished {
+                        println!(
+                            "Journal {} has index {} after finishing index {}",
+                            id, index, finished
+                        );
+                    }
+                }
+            }
+        }
+        self.entries.clear();
+    }
+}
@@ -1,15 +1,11 @@
-use crate::{context::Context, context::Mode, handlers::Handler};
+use crate::{context::Context, context::Mode, handlers::Handler, util::is_key};
 use std::collections::HashSet;
-use tes3::esp::{Cell, EditorId, MiscItem, MiscItemFlags, Reference, TES3Object};
+use tes3::esp::{Cell, EditorId, Reference, TES3Object};
 
 pub struct KeyValidator {
     miscs: HashSet<String>,
 }
 
-fn is_key(misc: &MiscItem) -> bool {
-    misc.data.flags.contains(MiscItemFlags::KEY)
-}
-
 impl Handler<'_> for KeyValidator {
     fn on_record(&mut self, context: &Context, record: &TES3Object) {
         if let TES3Object::MiscItem(misc) = record {
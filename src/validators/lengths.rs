@@ -0,0 +1,78 @@
+use super::Context;
+use crate::handlers::Handler;
+use tes3::esp::{Dialogue, DialogueInfo, EditorId, TES3Object, TypeInfo};
+
+// Longer strings are truncated or crash Morrowind.exe
+const MAX_ID_LENGTH: usize = 31;
+const MAX_NAME_LENGTH: usize = 31;
+const MAX_INFO_LENGTH: usize = 512;
+const MAX_SCRIPT_LENGTH: usize = 32 * 1024;
+
+pub struct LengthValidator {}
+
+fn check(record: &TES3Object, field: &str, value: &str, max: usize) {
+    let length = value.chars().count();
+    if length > max {
+        println!(
+            "{} {} has a {} of {} characters, which is longer than {}",
+            record.type_name(),
+            record.editor_id(),
+            field,
+            length,
+            max
+        );
+    }
+}
+
+impl Handler<'_> for LengthValidator {
+    fn on_record(&mut self, _: &Context, record: &TES3Object) {
+        match record {
+            TES3Object::Activator(r) => check(record, "name", &r.name, MAX_NAME_LENGTH),
+            TES3Object::Alchemy(r) => check(record, "name", &r.name, MAX_NAME_LENGTH),
+            TES3Object::Apparatus(r) => check(record, "name", &r.name, MAX_NAME_LENGTH),
+            TES3Object::Armor(r) => check(record, "name", &r.name, MAX_NAME_LENGTH),
+            TES3Object::Birthsign(r) => check(record, "name", &r.name, MAX_NAME_LENGTH),
+            TES3Object::Book(r) => check(record, "name", &r.name, MAX_NAME_LENGTH),
+            TES3Object::Class(r) => check(record, "name", &r.name, MAX_NAME_LENGTH),
+            TES3Object::Clothing(r) => check(record, "name", &r.name, MAX_NAME_LENGTH),
+            TES3Object::Container(r) => check(record, "name", &r.name, MAX_NAME_LENGTH),
+            TES3Object::Creature(r) => check(record, "name", &r.name, MAX_NAME_LENGTH),
+            TES3Object::Door(r) => check(record, "name", &r.name, MAX_NAME_LENGTH),
+            TES3Object::Faction(r) => check(record, "name", &r.name, MAX_NAME_LENGTH),
+            TES3Object::Ingredient(r) => check(record, "name", &r.name, MAX_NAME_LENGTH),
+            TES3Object::Light(r) => check(record, "name", &r.name, MAX_NAME_LENGTH),
+            TES3Object::Lockpick(r) => check(record, "name", &r.name, MAX_NAME_LENGTH),
+            TES3Object::MiscItem(r) => check(record, "name", &r.name, MAX_NAME_LENGTH),
+            TES3Object::Npc(r) => check(record, "name", &r.name, MAX_NAME_LENGTH),
+            TES3Object::Probe(r) => check(record, "name", &r.name, MAX_NAME_LENGTH),
+            TES3Object::Race(r) => check(record, "name", &r.name, MAX_NAME_LENGTH),
+            TES3Object::Region(r) => check(record, "name", &r.name, MAX_NAME_LENGTH),
+            TES3Object::RepairItem(r) => check(record, "name", &r.name, MAX_NAME_LENGTH),
+            TES3Object::Script(r) => check(record, "script_text", &r.text, MAX_SCRIPT_LENGTH),
+            TES3Object::Spell(r) => check(record, "name", &r.name, MAX_NAME_LENGTH),
+            TES3Object::Weapon(r) => check(record, "name", &r.name, MAX_NAME_LENGTH),
+            // Cell names are not object IDs and the other records' IDs are fixed by the engine
+            TES3Object::Cell(_)
+            | TES3Object::DialogueInfo(_)
+            | TES3Object::GameSetting(_)
+            | TES3Object::Header(_)
+            | TES3Object::Landscape(_)
+            | TES3Object::MagicEffect(_)
+            | TES3Object::PathGrid(_)
+            | TES3Object::Skill(_)
+            | TES3Object::StartScript(_) => return,
+            _ => {}
+        }
+        check(record, "id", &record.editor_id(), MAX_ID_LENGTH);
+    }
+
+    fn on_info(&mut self, _: &Context, record: &DialogueInfo, topic: &Dialogue) {
+        let length = record.text.chars().count();
+        if length > MAX_INFO_LENGTH {
+            println!(
+                "Info {} in topic {} has a text of {} characters, which is longer than {}",
+                record.id, topic.id, length, MAX_INFO_LENGTH
+            );
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::HashSet;
+
+use super::Context;
+use crate::{handlers::Handler, util::has_faction};
+use tes3::esp::{Dialogue, DialogueInfo, DialogueType, EditorId, TES3Object};
+
+const MACROS: [&str; 14] = [
+    "name",
+    "race",
+    "class",
+    "faction",
+    "rank",
+    "cell",
+    "pcname",
+    "pcrace",
+    "pcclass",
+    "pcrank",
+    "nextpcrank",
+    "pccrimelevel",
+    "crimegolddiscount",
+    "crimegoldturnin",
+];
+
+const SPEAKER_MACROS: [&str; 6] = ["name", "race", "class", "faction", "rank", "nextpcrank"];
+
+const FACTION_MACROS: [&str; 4] = ["faction", "rank", "pcrank", "nextpcrank"];
+
+pub struct MacroValidator {
+    globals: HashSet<String>,
+    unknown: Vec<(String, String, String)>,
+}
+
+fn get_macros(text: &str) -> impl Iterator<Item = &str> {
+    text.split('%').skip(1).filter_map(|part| {
+        let end = part
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(part.len());
+        let token = &part[..end];
+        if token.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            Some(token)
+        } else {
+            None
+        }
+    })
+}
+
+impl Handler<'_> for MacroValidator {
+    fn on_record(&mut self, _: &Context, record: &TES3Object) {
+        if let TES3Object::GlobalVariable(_) = record {
+            self.globals
+                .insert(record.editor_id_ascii_lowercase().into_owned());
+        }
+    }
+
+    fn on_info(&mut self, _: &Context, record: &DialogueInfo, topic: &Dialogue) {
+        let journal = record.data.dialogue_type == DialogueType::Journal;
+        let faction = has_faction(&record.speaker_faction) || !record.speaker_id.is_empty();
+        for token in get_macros(&record.text) {
+            let lower = token.to_ascii_lowercase();
+            if !MACROS.contains(&lower.as_str()) {
+                self.unknown
+                    .push((record.id.clone(), topic.id.clone(), token.to_string()));
+            } else if journal && SPEAKER_MACROS.contains(&lower.as_str()) {
+                println!(
+                    "Info {} in topic {} uses %{} in a journal entry",
+                    record.id, topic.id, token
+                );
+            } else if !journal && !faction && FACTION_MACROS.contains(&lower.as_str()) {
+                println!(
+                    "Info {} in topic {} uses %{} without a faction filter",
+                    record.id, topic.id, token
+                );
+            }
+        }
+    }
+
+    fn on_end(&mut self, _: &Context) {
+        for (id, topic, token) in &self.unknown {
+            if !self.globals.contains(&token.to_ascii_lowercase()) {
+                println!(
+                    "Info {} in topic {} uses unknown text macro %{}",
+                    id, topic, token
+                );
+            }
+        }
+    }
+}
+
+impl MacroValidator {
+    pub fn new() -> Self {
+        Self {
+            globals: HashSet::new(),
+            unknown: Vec::new(),
+        }
+    }
+}
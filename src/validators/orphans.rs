@@ -1,8 +1,12 @@
 use std::collections::{HashMap, HashSet};
 
 use super::Context;
-use crate::{context::Mode, handlers::Handler, util::update_or_insert};
-use regex::{Error, Regex};
+use crate::{
+    context::Mode,
+    handlers::Handler,
+    mwscript::{Expression, Statement, StatementKind},
+    util::update_or_insert,
+};
 use tes3::esp::{
     Cell, Dialogue, DialogueInfo, DialogueType2, EditorId, FixedString, QuestState, Reference,
     TES3Object, TypeInfo,
@@ -17,10 +21,6 @@ pub struct OrphanValidator {
     used_enchantments: HashSet<String>,
     journals: HashMap<String, HashSet<i32>>,
     used_journals: HashMap<String, Vec<i32>>,
-    startscript: Regex,
-    firstarg: Regex,
-    journal: Regex,
-    secondarg: Regex,
 }
 
 fn is_journal(dialogue: &Dialogue) -> bool {
@@ -165,34 +165,45 @@ impl Handler<'_> for OrphanValidator {
         self.used_objects.insert(entry.1.to_ascii_lowercase());
     }
 
-    fn on_scriptline(&mut self, _: &Context, _: &TES3Object, code: &str, _: &str, _: &Dialogue) {
-        if code.is_empty() {
-            return;
-        }
-        if let Some(captures) = self.startscript.captures(code) {
-            let id = captures.get(2).unwrap().as_str();
-            self.start_scripts.push(id.replace('"', ""));
-        } else if let Some(captures) = self.firstarg.captures(code) {
-            if let Some(quoted) = captures.get(3) {
-                self.used_objects.insert(quoted.as_str().replace('"', ""));
-            } else if let Some(unquoted) = captures.get(4) {
-                self.used_objects.insert(unquoted.as_str().to_string());
-            }
-        } else if let Some(captures) = self.journal.captures(code) {
-            let id;
-            if let Some(quoted) = captures.get(3) {
-                id = quoted.as_str().replace('"', "");
-            } else if let Some(unquoted) = captures.get(4) {
-                id = unquoted.as_str().to_string();
-            } else {
-                return;
-            }
-            if let Ok(index) = captures.get(5).unwrap().as_str().parse::<i32>() {
-                update_or_insert(&mut self.used_journals, id, |e| e.push(index));
+    fn on_script_ast(
+        &mut self,
+        _: &Context,
+        _: &TES3Object,
+        statements: &[Statement],
+        _: &Dialogue,
+    ) {
+        for statement in statements {
+            let StatementKind::Call(call) = &statement.kind else {
+                continue;
+            };
+            let function = call.function.to_ascii_lowercase();
+            match function.as_str() {
+                "startscript" => {
+                    if let Some(id) = call.get_id(0) {
+                        self.start_scripts.push(id.to_ascii_lowercase());
+                    }
+                }
+                "placeatme" | "addsoulgem" | "additem" | "equip" | "drop" | "placeatpc"
+                | "placeitemcell" | "placeitem" => {
+                    if let Some(id) = call.get_id(0) {
+                        self.used_objects.insert(id.to_ascii_lowercase());
+                    }
+                }
+                "journal" | "setjournalindex" => {
+                    let index = call.arguments.get(1).and_then(Expression::as_integer);
+                    if let (Some(id), Some(index)) = (call.get_id(0), index) {
+                        update_or_insert(&mut self.used_journals, id.to_ascii_lowercase(), |e| {
+                            e.push(index)
+                        });
+                    }
+                }
+                "addtolevcreature" | "addtolevitem" => {
+                    if let Some(id) = call.get_id(1) {
+                        self.used_objects.insert(id.to_ascii_lowercase());
+                    }
+                }
+                _ => {}
             }
-        } else if let Some(captures) = self.secondarg.captures(code) {
-            self.used_objects
-                .insert(captures.get(4).unwrap().as_str().replace('"', ""));
         }
     }
 
@@ -229,19 +240,8 @@ impl Handler<'_> for OrphanValidator {
 }
 
 impl OrphanValidator {
-    pub fn new() -> Result<Self, Error> {
-        let startscript =
-            Regex::new(r#"^([,\s]*|.*?->[,\s]*)startscript[,\s]+("[^"]+"|[^,\s]+)[,\s]*$"#)?;
-        let firstarg = Regex::new(
-            r#"^([,\s]*|.*?->[,\s]*)(placeatme|addsoulgem|additem|equip|drop|placeatpc|placeitemcell|placeitem)[,\s]+(?:("[^"]+"?)(?:.*)|([^,\s"]+)(?:[,\s]+|$))"#,
-        )?;
-        let journal = Regex::new(
-            r#"^([,\s]*|.*?->[,\s]*)(journal|setjournalindex)[,\s]+(?:("[^"]+"?)|([^,\s"]+))[,\s]+([\d]+)"#,
-        )?;
-        let secondarg = Regex::new(
-            r#"^([,\s]*|.*?->[,\s]*)(addtolevcreature|addtolevitem)[,\s]+("[^"]+"|[^,\s]+)[,\s]+("[^"]+"|[^,\s]+)([,\s]+|$)"#,
-        )?;
-        Ok(Self {
+    pub fn new() -> Self {
+        Self {
             script_ids: HashSet::new(),
             start_scripts: Vec::new(),
             objects: HashMap::new(),
@@ -250,11 +250,7 @@ impl OrphanValidator {
             used_enchantments: HashSet::new(),
             journals: HashMap::new(),
             used_journals: HashMap::new(),
-            startscript,
-            firstarg,
-            journal,
-            secondarg,
-        })
+        }
     }
 
     fn insert_object(&mut self, record: &TES3Object) {
@@ -1,14 +1,15 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use super::Context;
 use crate::{
     context::Mode,
     handlers::Handler,
-    util::{ci_starts_with, Actor},
+    mwscript::{Statement, StatementKind},
+    util::{ci_starts_with, get_script, Actor},
 };
 use codegen::{get_joined_commands, get_khajiit_script};
 use regex::{Regex, RegexBuilder};
-use tes3::esp::{Dialogue, Npc, Script, TES3Object};
+use tes3::esp::{Dialogue, EditorId, Npc, Script, TES3Object, TypeInfo};
 
 pub struct ScriptValidator {
     scripts: HashMap<String, ScriptInfo>,
@@ -20,7 +21,11 @@ pub struct ScriptValidator {
     projects: Vec<(&'static str, Regex)>,
     set_khajiit_neg1: Regex,
     set_khajiit_var: Regex,
-    position: Regex,
+    local_scripts: HashSet<String>,
+    started_scripts: HashMap<String, String>,
+    stopped_scripts: HashSet<String>,
+    attached_scripts: HashSet<String>,
+    every_frame: HashMap<String, Vec<String>>,
 }
 
 struct ScriptInfo {
@@ -47,6 +52,11 @@ impl ScriptInfo {
 
 impl Handler<'_> for ScriptValidator {
     fn on_record(&mut self, context: &Context, record: &TES3Object) {
+        if let Some(script) = get_script(record).filter(|s| !s.is_empty()) {
+            self.attached_scripts.insert(script.to_ascii_lowercase());
+        } else if let TES3Object::StartScript(r) = record {
+            self.attached_scripts.insert(r.script.to_ascii_lowercase());
+        }
         if context.mode == Mode::Vanilla {
             return;
         }
@@ -85,15 +95,40 @@ impl Handler<'_> for ScriptValidator {
         }
     }
 
-    fn on_scriptline(
+    fn on_script_ast(
         &mut self,
         _: &Context,
         record: &TES3Object,
-        code: &str,
-        _: &str,
+        statements: &[Statement],
         topic: &Dialogue,
     ) {
-        if !code.is_empty() && self.position.is_match(code) {
+        let uses_position = statements.iter().any(
+            |statement| matches!(&statement.kind, StatementKind::Call(call) if call.is("position")),
+        );
+        if let TES3Object::Script(script) = record {
+            self.check_performance(script, statements);
+            self.local_scripts.insert(script.id.to_ascii_lowercase());
+        }
+        for statement in statements {
+            statement.for_each_call(&mut |call| {
+                let Some(id) = call.get_id(0) else {
+                    return;
+                };
+                if call.is("startscript") {
+                    self.started_scripts
+                        .entry(id.to_ascii_lowercase())
+                        .or_insert_with(|| match record {
+                            TES3Object::DialogueInfo(info) => {
+                                format!("Info {} in topic {}", info.id, topic.id)
+                            }
+                            _ => format!("{} {}", record.type_name(), record.editor_id()),
+                        });
+                } else if call.is("stopscript") {
+                    self.stopped_scripts.insert(id.to_ascii_lowercase());
+                }
+            });
+        }
+        if uses_position {
             if let TES3Object::DialogueInfo(info) = record {
                 println!(
                     "Info {} in topic {} uses Position instead of PositionCell",
@@ -106,6 +141,18 @@ impl Handler<'_> for ScriptValidator {
     }
 
     fn on_end(&mut self, context: &Context) {
+        for (id, messages) in &self.every_frame {
+            if self.attached_scripts.contains(id) || self.started_scripts.contains_key(id) {
+                for message in messages {
+                    println!("{}", message);
+                }
+            }
+        }
+        for (id, source) in &self.started_scripts {
+            if self.local_scripts.contains(id) && !self.stopped_scripts.contains(id) {
+                println!("Script {} is started by {} but never stopped", id, source);
+            }
+        }
         if context.mode != Mode::TD {
             for (id, script) in &self.scripts {
                 if script.used && script.khajiit && !script.used_by_khajiit {
@@ -119,6 +166,41 @@ impl Handler<'_> for ScriptValidator {
     }
 }
 
+const EXPENSIVE_FUNCTIONS: [&str; 5] = [
+    "getdistance",
+    "getlos",
+    "getlineofsight",
+    "placeatpc",
+    "additem",
+];
+
+/// Functions that are cheap enough to guard more expensive code with
+const GUARD_FUNCTIONS: [&str; 3] = ["getdistance", "getlos", "getlineofsight"];
+
+/// Returns the problems with a statement that is evaluated every frame
+fn check_every_frame(script: &Script, statement: &Statement, messages: &mut Vec<String>) {
+    let condition = matches!(
+        statement.kind,
+        StatementKind::If(_) | StatementKind::ElseIf(_) | StatementKind::While(_)
+    );
+    statement.for_each_call(&mut |call| {
+        if condition && GUARD_FUNCTIONS.iter().any(|f| call.is(f)) {
+            return;
+        }
+        if call.is("getjournalindex") {
+            messages.push(format!(
+                "Script {} polls GetJournalIndex every frame on line {}",
+                script.id, statement.line
+            ));
+        } else if EXPENSIVE_FUNCTIONS.iter().any(|f| call.is(f)) {
+            messages.push(format!(
+                "Script {} calls {} every frame on line {}",
+                script.id, call.function, statement.line
+            ));
+        }
+    });
+}
+
 fn get_variable(name: &str, types: &str) -> Result<Regex, regex::Error> {
     RegexBuilder::new(&format!(
         "\n[\\s,]*{}[\\s,]+({})[\\s,]*(;*.?)\n",
@@ -151,7 +233,6 @@ impl ScriptValidator {
             RegexBuilder::new(r"\n\s*set\s+T_Local_Khajiit\s+to\s+([0-9-]+)\s*(;.*)?\n")
                 .case_insensitive(true)
                 .build()?;
-        let position = Regex::new(r"^([,\s]*|.*?->[,\s]*)position[,\s]+")?;
         Ok(Self {
             scripts: HashMap::new(),
             npc,
@@ -162,7 +243,11 @@ impl ScriptValidator {
             projects,
             set_khajiit_neg1,
             set_khajiit_var,
-            position,
+            local_scripts: HashSet::new(),
+            started_scripts: HashMap::new(),
+            stopped_scripts: HashSet::new(),
+            attached_scripts: HashSet::new(),
+            every_frame: HashMap::new(),
         })
     }
 
@@ -211,6 +296,39 @@ impl ScriptValidator {
         }
     }
 
+    fn check_performance(&mut self, script: &Script, statements: &[Statement]) {
+        // Anything outside of an if or while block, including their conditions, runs every frame
+        let mut depth: usize = 0;
+        let mut messages = Vec::new();
+        for statement in statements {
+            match &statement.kind {
+                StatementKind::If(_) | StatementKind::While(_) => {
+                    if depth == 0 {
+                        check_every_frame(script, statement, &mut messages);
+                    }
+                    depth += 1;
+                }
+                StatementKind::ElseIf(_) if depth == 1 => {
+                    check_every_frame(script, statement, &mut messages);
+                }
+                StatementKind::EndIf | StatementKind::EndWhile => {
+                    depth = depth.saturating_sub(1);
+                }
+                StatementKind::Set(_, _) | StatementKind::Call(_) if depth == 0 => {
+                    check_every_frame(script, statement, &mut messages);
+                }
+                // The rest of the script is guarded by an early return like if ( doOnce ) return endif
+                StatementKind::Return if depth <= 1 => break,
+                _ => {}
+            }
+        }
+        if !messages.is_empty() {
+            // Only object scripts and running global scripts are executed every frame
+            self.every_frame
+                .insert(script.id.to_ascii_lowercase(), messages);
+        }
+    }
+
     fn has_correct_khajiit_check(&self, record: &Script, text: &str) -> bool {
         if self.set_khajiit_neg1.is_match(text) {
             return self.khajiit_script.is_match(text);
@@ -0,0 +1,165 @@
+use super::Context;
+use crate::{handlers::Handler, util::ci_starts_with};
+use tes3::esp::{
+    Dialogue, DialogueInfo, DialogueType, Filter, FilterComparison, FilterValue, ObjectFlags, Sex,
+};
+
+pub struct ShadowValidator<'a> {
+    topic: String,
+    infos: Vec<&'a DialogueInfo>,
+}
+
+fn get_float(value: FilterValue) -> f32 {
+    match value {
+        FilterValue::Integer(i) => i as f32,
+        FilterValue::Float(f) => f,
+    }
+}
+
+fn satisfies(comparison: FilterComparison, value: f32, x: f32) -> bool {
+    match comparison {
+        FilterComparison::Equal => x == value,
+        FilterComparison::NotEqual => x != value,
+        FilterComparison::Greater => x > value,
+        FilterComparison::GreaterEqual => x >= value,
+        FilterComparison::Less => x < value,
+        FilterComparison::LessEqual => x <= value,
+    }
+}
+
+fn same_variable(a: &Filter, b: &Filter) -> bool {
+    a.filter_type == b.filter_type && a.function == b.function && a.id.eq_ignore_ascii_case(&b.id)
+}
+
+/// Returns true if every value that passes b also passes a
+fn filter_implies(b: &Filter, a: &Filter) -> bool {
+    if !same_variable(a, b) {
+        return false;
+    }
+    let a_value = get_float(a.value);
+    let b_value = get_float(b.value);
+    match (b.comparison, a.comparison) {
+        (FilterComparison::Equal, _) => satisfies(a.comparison, a_value, b_value),
+        (FilterComparison::NotEqual, FilterComparison::NotEqual) => a_value == b_value,
+        (
+            FilterComparison::Greater,
+            FilterComparison::Greater | FilterComparison::GreaterEqual | FilterComparison::NotEqual,
+        ) => a_value <= b_value,
+        (FilterComparison::GreaterEqual, FilterComparison::GreaterEqual) => a_value <= b_value,
+        (
+            FilterComparison::GreaterEqual,
+            FilterComparison::Greater | FilterComparison::NotEqual,
+        ) => a_value < b_value,
+        (
+            FilterComparison::Less,
+            FilterComparison::Less | FilterComparison::LessEqual | FilterComparison::NotEqual,
+        ) => a_value >= b_value,
+        (FilterComparison::LessEqual, FilterComparison::LessEqual) => a_value >= b_value,
+        (FilterComparison::LessEqual, FilterComparison::Less | FilterComparison::NotEqual) => {
+            a_value > b_value
+        }
+        _ => false,
+    }
+}
+
+/// Returns true if no value passes both a and b
+fn filters_contradict(a: &Filter, b: &Filter) -> bool {
+    if !same_variable(a, b) {
+        return false;
+    }
+    let a_value = get_float(a.value);
+    let b_value = get_float(b.value);
+    if a.comparison == FilterComparison::Equal {
+        return !satisfies(b.comparison, b_value, a_value);
+    } else if b.comparison == FilterComparison::Equal {
+        return !satisfies(a.comparison, a_value, b_value);
+    }
+    let (lower, upper) = match (a.comparison, b.comparison) {
+        (
+            FilterComparison::Greater | FilterComparison::GreaterEqual,
+            FilterComparison::Less | FilterComparison::LessEqual,
+        ) => (a, b),
+        (
+            FilterComparison::Less | FilterComparison::LessEqual,
+            FilterComparison::Greater | FilterComparison::GreaterEqual,
+        ) => (b, a),
+        _ => return false,
+    };
+    let lower_value = get_float(lower.value);
+    let upper_value = get_float(upper.value);
+    lower_value > upper_value
+        || lower_value == upper_value
+            && (lower.comparison == FilterComparison::Greater
+                || upper.comparison == FilterComparison::Less)
+}
+
+fn field_implies(b: &str, a: &str) -> bool {
+    a.is_empty() || a.eq_ignore_ascii_case(b)
+}
+
+fn rank_implies(b: i8, a: i8) -> bool {
+    a < 0 || b >= a
+}
+
+/// Returns true if the game will pick info a whenever info b's conditions are met
+pub fn shadows(a: &DialogueInfo, b: &DialogueInfo) -> bool {
+    field_implies(&b.speaker_id, &a.speaker_id)
+        && field_implies(&b.speaker_race, &a.speaker_race)
+        && field_implies(&b.speaker_class, &a.speaker_class)
+        && field_implies(&b.speaker_faction, &a.speaker_faction)
+        && field_implies(&b.player_faction, &a.player_faction)
+        && (a.speaker_cell.is_empty() || ci_starts_with(&b.speaker_cell, &a.speaker_cell))
+        && (a.data.speaker_sex == Sex::Any || a.data.speaker_sex == b.data.speaker_sex)
+        && a.data.disposition <= b.data.disposition
+        && rank_implies(b.data.speaker_rank, a.data.speaker_rank)
+        && rank_implies(b.data.player_rank, a.data.player_rank)
+        && a.filters
+            .iter()
+            .all(|filter| b.filters.iter().any(|other| filter_implies(other, filter)))
+}
+
+/// Returns true if the info can be selected by picking the first matching info in its topic
+pub fn is_selectable(info: &DialogueInfo) -> bool {
+    matches!(
+        info.data.dialogue_type,
+        DialogueType::Topic | DialogueType::Greeting | DialogueType::Persuasion
+    ) && !info.flags.contains(ObjectFlags::DELETED)
+}
+
+impl<'a> Handler<'a> for ShadowValidator<'a> {
+    fn on_info(&mut self, _: &Context, record: &'a DialogueInfo, topic: &Dialogue) {
+        if topic.id != self.topic {
+            self.infos.clear();
+            topic.id.clone_into(&mut self.topic);
+        }
+        if !is_selectable(record) {
+            return;
+        }
+        let contradicts = record.filters.iter().enumerate().any(|(i, filter)| {
+            record.filters[i + 1..]
+                .iter()
+                .any(|other| filters_contradict(filter, other))
+        });
+        if contradicts {
+            println!(
+                "Info {} in topic {} has contradictory filters",
+                record.id, topic.id
+            );
+        } else if let Some(other) = self.infos.iter().find(|other| shadows(other, record)) {
+            println!(
+                "Info {} in topic {} is shadowed by info {}",
+                record.id, topic.id, other.id
+            );
+        }
+        self.infos.push(record);
+    }
+}
+
+impl ShadowValidator<'_> {
+    pub fn new<'a>() -> ShadowValidator<'a> {
+        ShadowValidator {
+            topic: String::new(),
+            infos: Vec::new(),
+        }
+    }
+}
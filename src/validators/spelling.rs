@@ -0,0 +1,280 @@
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fs,
+};
+
+use super::Context;
+use crate::handlers::Handler;
+use clap::ArgMatches;
+use codegen::get_dictionary;
+use levenshtein::levenshtein;
+use regex::Regex;
+use tes3::esp::{Dialogue, DialogueInfo, EditorId, TES3Object, TypeInfo};
+
+const SUFFIXES: [(&str, &str); 26] = [
+    ("s", ""),
+    ("es", ""),
+    ("ies", "y"),
+    ("ed", ""),
+    ("ed", "e"),
+    ("ied", "y"),
+    ("ing", ""),
+    ("ing", "e"),
+    ("ly", ""),
+    ("ily", "y"),
+    ("er", ""),
+    ("er", "e"),
+    ("ier", "y"),
+    ("est", ""),
+    ("est", "e"),
+    ("iest", "y"),
+    ("ness", ""),
+    ("iness", "y"),
+    ("less", ""),
+    ("ful", ""),
+    ("ment", ""),
+    ("able", ""),
+    ("able", "e"),
+    ("ish", ""),
+    ("'s", ""),
+    ("s'", "s"),
+];
+
+const PREFIXES: [&str; 7] = ["un", "re", "dis", "mis", "over", "under", "non"];
+
+const MAX_SUGGESTIONS: usize = 3;
+
+struct Misspelling {
+    source: String,
+    field: &'static str,
+    word: String,
+}
+
+pub struct SpellValidator {
+    words: HashSet<String>,
+    names: HashSet<String>,
+    tags: Regex,
+    misspellings: Vec<Misspelling>,
+}
+
+fn get_words(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'' && c != '%')
+        .map(|word| word.trim_matches('\''))
+        .filter(|word| {
+            word.len() > 1
+                && !word.starts_with('%')
+                && word.chars().all(|c| c.is_alphabetic() || c == '\'')
+        })
+}
+
+fn add_words(words: &mut HashSet<String>, text: &str) {
+    for word in text.split(|c: char| c.is_whitespace() || c == '-') {
+        let word = word.trim_matches('\'');
+        if !word.is_empty() {
+            words.insert(word.to_lowercase());
+        }
+    }
+}
+
+/// Returns true if the words only differ by two swapped neighbouring letters
+fn is_swap(word: &str, candidate: &str) -> bool {
+    let a: Vec<_> = word.chars().collect();
+    let b: Vec<_> = candidate.chars().collect();
+    if a.len() != b.len() {
+        return false;
+    }
+    let differences: Vec<_> = (0..a.len()).filter(|i| a[*i] != b[*i]).collect();
+    matches!(differences[..], [i, j] if j == i + 1 && a[i] == b[j] && a[j] == b[i])
+}
+
+impl Handler<'_> for SpellValidator {
+    fn on_record(&mut self, _: &Context, record: &TES3Object) {
+        match record {
+            TES3Object::Activator(r) => self.check(record, "name", &r.name),
+            TES3Object::Alchemy(r) => self.check(record, "name", &r.name),
+            TES3Object::Apparatus(r) => self.check(record, "name", &r.name),
+            TES3Object::Armor(r) => self.check(record, "name", &r.name),
+            TES3Object::Birthsign(r) => {
+                self.check(record, "name", &r.name);
+                self.check(record, "description", &r.description);
+            }
+            TES3Object::Book(r) => {
+                self.check(record, "name", &r.name);
+                let text = self.tags.replace_all(&r.text, " ").into_owned();
+                self.check(record, "text", &text);
+            }
+            TES3Object::Class(r) => {
+                self.check(record, "name", &r.name);
+                self.check(record, "description", &r.description);
+            }
+            TES3Object::Clothing(r) => self.check(record, "name", &r.name),
+            TES3Object::Container(r) => self.check(record, "name", &r.name),
+            TES3Object::Creature(r) => self.check(record, "name", &r.name),
+            TES3Object::Door(r) => self.check(record, "name", &r.name),
+            TES3Object::Faction(r) => self.check(record, "name", &r.name),
+            TES3Object::Ingredient(r) => self.check(record, "name", &r.name),
+            TES3Object::Light(r) => self.check(record, "name", &r.name),
+            TES3Object::Lockpick(r) => self.check(record, "name", &r.name),
+            TES3Object::MiscItem(r) => self.check(record, "name", &r.name),
+            TES3Object::Npc(r) => {
+                // Personal names are not checked, but are accepted when they appear elsewhere
+                add_words(&mut self.names, &r.name);
+            }
+            TES3Object::Probe(r) => self.check(record, "name", &r.name),
+            TES3Object::Race(r) => {
+                self.check(record, "name", &r.name);
+                self.check(record, "description", &r.description);
+            }
+            TES3Object::Region(r) => self.check(record, "name", &r.name),
+            TES3Object::RepairItem(r) => self.check(record, "name", &r.name),
+            TES3Object::Spell(r) => self.check(record, "name", &r.name),
+            TES3Object::Weapon(r) => self.check(record, "name", &r.name),
+            _ => {}
+        }
+    }
+
+    fn on_info(&mut self, _: &Context, record: &DialogueInfo, topic: &Dialogue) {
+        let source = format!("Info {} in topic {}", record.id, topic.id);
+        for word in get_words(&record.text) {
+            if !self.is_known(&word.to_lowercase(), 2) {
+                self.misspellings.push(Misspelling {
+                    source: source.clone(),
+                    field: "text",
+                    word: word.to_string(),
+                });
+            }
+        }
+    }
+
+    fn on_end(&mut self, _: &Context) {
+        let mut suggestions = HashMap::new();
+        let mut reported = HashSet::new();
+        for misspelling in &self.misspellings {
+            let lower = misspelling.word.to_lowercase();
+            if self.names.contains(&lower)
+                || !reported.insert((&misspelling.source, misspelling.field, lower.clone()))
+            {
+                continue;
+            }
+            let suggestion = suggestions
+                .entry(lower)
+                .or_insert_with_key(|word| self.suggest(word));
+            if suggestion.is_empty() {
+                println!(
+                    "{} contains unknown word {} in field {}",
+                    misspelling.source, misspelling.word, misspelling.field
+                );
+            } else {
+                println!(
+                    "{} contains unknown word {} in field {} (did you mean {}?)",
+                    misspelling.source,
+                    misspelling.word,
+                    misspelling.field,
+                    suggestion.join(", ")
+                );
+            }
+        }
+    }
+}
+
+impl SpellValidator {
+    pub fn new(args: &ArgMatches) -> Result<Self, Box<dyn Error>> {
+        let mut words: HashSet<String> = get_dictionary!().into_iter().map(String::from).collect();
+        if let Some(path) = args.get_one::<String>("dictionary") {
+            let contents = fs::read_to_string(path)
+                .map_err(|e| format!("Failed to load dictionary {} ({})", path, e))?;
+            for line in contents.lines() {
+                if !line.starts_with('#') {
+                    add_words(&mut words, line);
+                }
+            }
+        }
+        let tags = Regex::new(r"<[^>]*>")?;
+        Ok(Self {
+            words,
+            names: HashSet::new(),
+            tags,
+            misspellings: Vec::new(),
+        })
+    }
+
+    fn check(&mut self, record: &TES3Object, field: &'static str, text: &str) {
+        for word in get_words(text) {
+            if !self.is_known(&word.to_lowercase(), 2) {
+                self.misspellings.push(Misspelling {
+                    source: format!("{} {}", record.type_name(), record.editor_id()),
+                    field,
+                    word: word.to_string(),
+                });
+            }
+        }
+    }
+
+    /// Returns true if the word or a word it was derived from is in the dictionary
+    fn is_known(&self, word: &str, depth: usize) -> bool {
+        if self.words.contains(word) {
+            return true;
+        } else if depth == 0 {
+            return false;
+        }
+        for (suffix, replacement) in SUFFIXES {
+            let Some(stem) = word.strip_suffix(suffix) else {
+                continue;
+            };
+            if stem.len() < 2 {
+                continue;
+            }
+            if self.is_known(&format!("{}{}", stem, replacement), depth - 1) {
+                return true;
+            }
+            // Doubled consonants as in stopped or bigger
+            let mut chars = stem.chars().rev();
+            let last = chars.next();
+            if replacement.is_empty()
+                && last == chars.next()
+                && last
+                    .is_some_and(|c| self.is_known(&stem[..stem.len() - c.len_utf8()], depth - 1))
+            {
+                return true;
+            }
+        }
+        PREFIXES.iter().any(|prefix| {
+            word.strip_prefix(prefix)
+                .is_some_and(|stem| stem.len() > 2 && self.is_known(stem, depth - 1))
+        })
+    }
+
+    fn suggest(&self, word: &str) -> Vec<&str> {
+        let first = word.chars().next();
+        let mut candidates: Vec<_> = self
+            .words
+            .iter()
+            .filter(|candidate| candidate.len().abs_diff(word.len()) <= 2)
+            .filter_map(|candidate| {
+                let swap = is_swap(word, candidate);
+                let distance = if swap {
+                    1
+                } else {
+                    levenshtein(word, candidate)
+                };
+                let other_start = candidate.chars().next() != first;
+                let length = candidate.len().abs_diff(word.len());
+                // Prefer swapped letters, then words that start the same
+                (distance <= 2).then_some((
+                    distance,
+                    !swap,
+                    other_start,
+                    length,
+                    candidate.as_str(),
+                ))
+            })
+            .collect();
+        candidates.sort_unstable();
+        candidates
+            .into_iter()
+            .take(MAX_SUGGESTIONS)
+            .map(|(_, _, _, _, candidate)| candidate)
+            .collect()
+    }
+}
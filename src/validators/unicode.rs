@@ -1,10 +1,84 @@
+use std::{borrow::Cow, error::Error, fs};
+
 use super::Context;
-use crate::handlers::Handler;
+use crate::{
+    encoding::Encoding,
+    handlers::Handler,
+    mwscript::{find_strings, shows_strings},
+};
+use clap::ArgMatches;
+use codegen::get_term_rules;
 use regex::Regex;
-use tes3::esp::{Dialogue, DialogueInfo, EditorId, TES3Object, TypeInfo};
+use serde::Deserialize;
+use tes3::esp::{Dialogue, DialogueInfo, DialogueType2, EditorId, TES3Object, TypeInfo};
+
+const SCOPES: [&str; 7] = [
+    "all",
+    "books",
+    "descriptions",
+    "dialogue",
+    "journal",
+    "names",
+    "scripts",
+];
+
+struct TermRule {
+    pattern: &'static str,
+    replacement: &'static str,
+    scope: &'static str,
+}
+
+fn default_scope() -> String {
+    String::from("all")
+}
+
+/// A rule from a --terms file, in the same format as crates/codegen/data/terms.json
+#[derive(Deserialize)]
+struct TermFileRule {
+    pattern: String,
+    replacement: String,
+    #[serde(default = "default_scope")]
+    scope: String,
+}
+
+struct Term {
+    pattern: Regex,
+    replacement: String,
+    scope: String,
+}
+
+impl Term {
+    fn new(pattern: &str, replacement: &str, scope: &str) -> Result<Self, String> {
+        if !SCOPES.contains(&scope) {
+            return Err(format!("Invalid scope {} for term {}", scope, pattern));
+        }
+        let regex = Regex::new(pattern).map_err(|e| format!("Invalid term {} ({})", pattern, e))?;
+        Ok(Self {
+            pattern: regex,
+            replacement: replacement.to_string(),
+            scope: scope.to_string(),
+        })
+    }
+}
 
 pub struct UnicodeValidator {
-    invalid: Regex,
+    encoding: Encoding,
+    french: bool,
+    terms: Vec<Term>,
+    tags: Regex,
+}
+
+/// Returns the kind of player-visible text a field contains
+fn get_scope(field: &str, topic: Option<&Dialogue>) -> Option<&'static str> {
+    match (field, topic) {
+        ("name", _) => Some("names"),
+        ("description", _) => Some("descriptions"),
+        ("text", None) => Some("books"),
+        ("text", Some(dial)) if dial.dialogue_type == DialogueType2::Journal => Some("journal"),
+        ("text", Some(_)) => Some("dialogue"),
+        ("script_text", _) => Some("scripts"),
+        _ => None,
+    }
 }
 
 impl Handler<'_> for UnicodeValidator {
@@ -114,23 +188,52 @@ impl Handler<'_> for UnicodeValidator {
 }
 
 impl UnicodeValidator {
-    pub fn new() -> Result<Self, regex::Error> {
-        let invalid = Regex::new(r"[\u0000-\u0008\u000b\u000c\u000e-\u001f\u007f-\uffff]")?;
-        Ok(Self { invalid })
+    pub fn new(context: &Context, args: &ArgMatches) -> Result<Self, Box<dyn Error>> {
+        let rules: Vec<TermRule> = get_term_rules!();
+        let mut terms = Vec::with_capacity(rules.len());
+        for rule in rules {
+            terms.push(Term::new(rule.pattern, rule.replacement, rule.scope)?);
+        }
+        if let Some(path) = args.get_one::<String>("terms") {
+            let contents = fs::read_to_string(path)
+                .map_err(|e| format!("Failed to load terms {} ({})", path, e))?;
+            let rules: Vec<TermFileRule> = serde_json::from_str(&contents)
+                .map_err(|e| format!("Failed to parse terms {} ({})", path, e))?;
+            for rule in rules {
+                let term = Term::new(&rule.pattern, &rule.replacement, &rule.scope)?;
+                // Rules for the same pattern replace the built-in ones
+                match terms
+                    .iter_mut()
+                    .find(|t| t.pattern.as_str() == term.pattern.as_str())
+                {
+                    Some(existing) => *existing = term,
+                    None => terms.push(term),
+                }
+            }
+        }
+        Ok(Self {
+            encoding: context.encoding,
+            french: context.french,
+            terms,
+            tags: Regex::new(r"<[^>]*>")?,
+        })
     }
 
     fn test<T>(&self, record: &T, field: &str, value: &str, topic: Option<&Dialogue>)
     where
         T: EditorId + TypeInfo,
     {
-        if let Some(m) = self.invalid.find(value) {
+        let value = self.encoding.decode(value);
+        // French puts non-breaking spaces before some punctuation
+        let is_valid = |c: char| self.encoding.is_valid(c) || self.french && c == '\u{a0}';
+        if let Some(c) = value.chars().find(|c| !is_valid(*c)) {
             if let Some(dial) = topic {
                 println!(
                     "{} {} in topic {} contains odd character {} in field {}",
                     record.type_name(),
                     record.editor_id(),
                     dial.id,
-                    m.as_str(),
+                    c,
                     field
                 );
             } else {
@@ -138,10 +241,55 @@ impl UnicodeValidator {
                     "{} {} contains odd character {} in field {}",
                     record.type_name(),
                     record.editor_id(),
-                    m.as_str(),
+                    c,
                     field
                 );
             }
         }
+        let Some(scope) = get_scope(field, topic) else {
+            return;
+        };
+        let source = if let Some(dial) = topic {
+            format!(
+                "{} {} in topic {}",
+                record.type_name(),
+                record.editor_id(),
+                dial.id
+            )
+        } else {
+            format!("{} {}", record.type_name(), record.editor_id())
+        };
+        // Only the strings scripts show to the player and the text outside book tags are checked
+        let texts: Vec<Cow<str>> = match scope {
+            "scripts" => value
+                .lines()
+                .filter(|line| shows_strings(line))
+                .flat_map(|line| find_strings(line).into_iter().map(|r| line[r].into()))
+                .collect(),
+            "books" => vec![self.tags.replace_all(&value, " ")],
+            _ => vec![Cow::Borrowed(value.as_ref())],
+        };
+        for term in &self.terms {
+            if term.scope != "all" && term.scope != scope {
+                continue;
+            }
+            for m in texts.iter().flat_map(|text| term.pattern.find_iter(text)) {
+                let found = m.as_str();
+                if term.replacement.is_empty() {
+                    println!(
+                        "{} contains forbidden term {} in field {}",
+                        source, found, field
+                    );
+                    continue;
+                }
+                let replacement = term.pattern.replace(found, term.replacement.as_str());
+                if replacement != found {
+                    println!(
+                        "{} contains {} in field {} which should be {}",
+                        source, found, field, replacement
+                    );
+                }
+            }
+        }
     }
 }